@@ -0,0 +1,262 @@
+//! Rust code for the docs, produced by the same `generator` functions ash uses to write `vk.rs`.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+
+/// The vkxml view of the registry along with the cross-item state ash's generator expects.
+pub(crate) struct AshGenerator {
+    definitions: HashMap<String, vkxml::DefinitionsElement>,
+    /// Structs that other structs can extend, which get a `push_next` setter.
+    root_structs: HashSet<Ident>,
+    union_types: HashSet<String>,
+    /// Structs and unions that hold references, directly or through their members.
+    has_lifetimes: HashSet<Ident>,
+}
+
+impl AshGenerator {
+    pub fn new(registry: &vkxml::Registry) -> Self {
+        let mut definitions = HashMap::new();
+        for element in registry.elements.iter() {
+            let vkxml::RegistryElement::Definitions(elements) = element else {
+                continue;
+            };
+            for definition in elements.elements.iter() {
+                let name = match definition {
+                    vkxml::DefinitionsElement::Struct(s) => &s.name,
+                    vkxml::DefinitionsElement::Union(u) => &u.name,
+                    vkxml::DefinitionsElement::Handle(h) => &h.name,
+                    vkxml::DefinitionsElement::FuncPtr(f) => &f.name,
+                    vkxml::DefinitionsElement::Bitmask(b) => &b.name,
+                    _ => continue,
+                };
+                definitions.insert(name.clone(), definition.clone());
+            }
+        }
+
+        let mut root_structs = HashSet::new();
+        let mut union_types = HashSet::new();
+        let mut members: Vec<(Ident, Vec<&vkxml::Field>)> = Vec::new();
+        for definition in definitions.values() {
+            match definition {
+                vkxml::DefinitionsElement::Struct(s) => {
+                    if let Some(extends) = &s.extends {
+                        root_structs.extend(extends.split(',').map(name_ident));
+                    }
+                    let fields = s
+                        .elements
+                        .iter()
+                        .filter_map(|element| match element {
+                            vkxml::StructElement::Member(field) => Some(field),
+                            _ => None,
+                        })
+                        .collect();
+                    members.push((name_ident(&s.name), fields));
+                }
+                vkxml::DefinitionsElement::Union(u) => {
+                    union_types.insert(u.name.clone());
+                    members.push((name_ident(&u.name), u.elements.iter().collect()));
+                }
+                _ => (),
+            }
+        }
+
+        // Same as ash: anything holding a pointer borrows, and so does anything containing such a type.
+        let mut has_lifetimes = members
+            .iter()
+            .filter(|(_, fields)| fields.iter().any(|field| field.reference.is_some()))
+            .map(|(ident, _)| ident.clone())
+            .collect::<HashSet<_>>();
+        loop {
+            let inherited = members
+                .iter()
+                .filter(|(ident, _)| !has_lifetimes.contains(ident))
+                .filter(|(_, fields)| {
+                    fields
+                        .iter()
+                        .any(|field| has_lifetimes.contains(&name_ident(&field.basetype)))
+                })
+                .map(|(ident, _)| ident.clone())
+                .collect::<Vec<_>>();
+            if inherited.is_empty() {
+                break;
+            }
+            has_lifetimes.extend(inherited);
+        }
+
+        Self {
+            definitions,
+            root_structs,
+            union_types,
+            has_lifetimes,
+        }
+    }
+
+    pub fn has_lifetime(&self, name: &str) -> bool {
+        self.has_lifetimes.contains(&name_ident(name))
+    }
+
+    /// A struct or union with its `Default` impl, `TaggedStructure` impl and builder setters.
+    pub fn struct_items(&self, ty: &vk_parse::Type) -> Vec<syn::Item> {
+        let name = ty.name.as_ref().unwrap();
+        let tokens = match self.definitions.get(name) {
+            Some(vkxml::DefinitionsElement::Struct(s)) => {
+                let vk_parse_types = HashMap::from([(name.clone(), ty)]);
+                let union_types = self.union_types.iter().map(String::as_str).collect();
+                generator::generate_struct(
+                    s,
+                    &vk_parse_types,
+                    &self.root_structs,
+                    &union_types,
+                    &self.has_lifetimes,
+                )
+            }
+            Some(vkxml::DefinitionsElement::Union(u)) => {
+                generator::generate_union(u, &self.root_structs, &self.has_lifetimes)
+            }
+            _ => return Vec::new(),
+        };
+        parse_items(tokens)
+    }
+
+    pub fn handle_items(&self, name: &str) -> Vec<syn::Item> {
+        let Some(vkxml::DefinitionsElement::Handle(handle)) = self.definitions.get(name) else {
+            return Vec::new();
+        };
+        generator::generate_handle(handle).map(parse_items).unwrap_or_default()
+    }
+
+    pub fn funcptr_items(&self, name: &str) -> Vec<syn::Item> {
+        let Some(vkxml::DefinitionsElement::FuncPtr(fnptr)) = self.definitions.get(name) else {
+            return Vec::new();
+        };
+        parse_items(generator::generate_funcptr(fnptr, &self.has_lifetimes))
+    }
+
+    /// A flags type without bits, which ash declares through `vk_bitflags_wrapped!` alone.
+    pub fn bitmask_items(&self, name: &str) -> Vec<syn::Item> {
+        let Some(vkxml::DefinitionsElement::Bitmask(bitmask)) = self.definitions.get(name) else {
+            return Vec::new();
+        };
+        let mut bitflags_cache = HashSet::new();
+        let mut const_values = BTreeMap::new();
+        generator::generate_bitmask(bitmask, &mut bitflags_cache, &mut const_values)
+            .map(parse_items)
+            .unwrap_or_default()
+    }
+
+    /// An enum, or the flags type generated from a `FlagBits` enum.
+    pub fn enum_items(&self, enums: &vk_parse::Enums) -> Vec<syn::Item> {
+        let mut const_cache = HashSet::new();
+        let mut const_values = BTreeMap::new();
+        let mut bitflags_cache = HashSet::new();
+        let tokens = match generator::generate_enum(
+            enums,
+            &mut const_cache,
+            &mut const_values,
+            &mut bitflags_cache,
+        ) {
+            generator::EnumType::Enum(tokens) => tokens,
+            generator::EnumType::Bitflags(tokens) => tokens,
+        };
+        parse_items(tokens)
+    }
+
+    /// Bits added to a flags type by a feature or extension, in the separate `impl` block ash
+    /// generates for them. Each constant is either a value or an alias of another constant.
+    pub fn flag_constant_items(
+        &self,
+        flags: &str,
+        provider: &str,
+        constants: &[(String, u64, Option<String>)],
+    ) -> Vec<syn::Item> {
+        let flags = name_ident(flags);
+        let doc = format!("Generated from '{provider}'");
+        let constants = constants.iter().map(|(name, value, alias)| {
+            let name = format_ident!("{}", name);
+            match alias {
+                Some(alias) => {
+                    let alias = format_ident!("{}", alias);
+                    quote!(pub const #name: Self = Self::#alias;)
+                }
+                None => {
+                    let value = syn::LitInt::new(&binary_literal(*value), proc_macro2::Span::call_site());
+                    quote!(pub const #name: Self = Self(#value);)
+                }
+            }
+        });
+        parse_items(quote! {
+            #[doc = #doc]
+            impl #flags {
+                #(#constants)*
+            }
+        })
+    }
+
+    /// The `PFN_vk*` type ash loads a command into. Aliases share the type of the command they
+    /// alias, as in ash, so `command` is the definition the alias resolves to.
+    pub fn command_items(&self, command: &vk_parse::CommandDefinition) -> Vec<syn::Item> {
+        let pfn = format_ident!("PFN_{}", command.proto.name);
+        let mut fn_cache = HashSet::new();
+        let tokens = generator::generate_function_pointers(
+            format_ident!("{}Fn", command.proto.name),
+            &[command],
+            &HashMap::new(),
+            &mut fn_cache,
+            &self.has_lifetimes,
+        );
+        parse_items(tokens)
+            .into_iter()
+            .filter(|item| matches!(item, syn::Item::Type(ty) if ty.ident == pfn))
+            .collect()
+    }
+}
+
+/// Pretty-prints items the way `rustfmt` would format ash's generated sources.
+pub(crate) fn unparse(items: Vec<syn::Item>) -> String {
+    let file = syn::File {
+        shebang: None,
+        attrs: Vec::new(),
+        items,
+    };
+    prettyplease::unparse(&file).trim_end().to_string()
+}
+
+/// Whether an item generated for a struct belongs to its builder API rather than its definition:
+/// the setters, the `TaggedStructure` impl and the `Extends*` traits checked by `push_next`.
+pub(crate) fn is_builder_item(item: &syn::Item) -> bool {
+    match item {
+        syn::Item::Trait(item) => item.ident.to_string().starts_with("Extends"),
+        syn::Item::Impl(item) => match &item.trait_ {
+            None => true,
+            Some((_, path, _)) => path.segments.last().map_or(false, |segment| {
+                let ident = segment.ident.to_string();
+                ident == "TaggedStructure" || ident.starts_with("Extends")
+            }),
+        },
+        _ => false,
+    }
+}
+
+fn parse_items(tokens: TokenStream) -> Vec<syn::Item> {
+    syn::parse2::<syn::File>(tokens).unwrap().items
+}
+
+/// Binary literal grouped in nibbles the way ash writes bit values, e.g. `0b1_0000`.
+fn binary_literal(value: u64) -> String {
+    let digits = format!("{value:b}");
+    let mut groups = Vec::new();
+    let mut end = digits.len();
+    while end > 4 {
+        groups.push(&digits[end - 4..end]);
+        end -= 4;
+    }
+    groups.push(&digits[..end]);
+    groups.reverse();
+    format!("0b{}", groups.join("_"))
+}
+
+fn name_ident(name: &str) -> Ident {
+    format_ident!("{}", name.strip_prefix("Vk").unwrap_or(name))
+}
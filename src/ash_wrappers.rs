@@ -0,0 +1,335 @@
+//! Mapping from commands to the ash API that exposes them: a method of `ash::Entry`,
+//! `ash::Instance`, `ash::Device` or of an extension wrapper, or else a raw function pointer table.
+
+use heck::ToSnakeCase;
+
+use crate::{c_decl, Converter, DispatchLevel};
+
+/// docs.rs root of the ash release matching the `generator` revision in Cargo.toml.
+const ASH_DOCS: &str = "https://docs.rs/ash/0.38.0+1.3.281/ash";
+
+/// Extensions with hand-written wrappers in ash's `src/extensions`, as `vendor/name` paths.
+const ASH_EXTENSION_WRAPPERS: &[&str] = &[
+    "amd/buffer_marker",
+    "amd/shader_info",
+    "amdx/shader_enqueue",
+    "android/external_memory_android_hardware_buffer",
+    "ext/acquire_drm_display",
+    "ext/buffer_device_address",
+    "ext/calibrated_timestamps",
+    "ext/debug_marker",
+    "ext/debug_report",
+    "ext/debug_utils",
+    "ext/descriptor_buffer",
+    "ext/extended_dynamic_state",
+    "ext/extended_dynamic_state2",
+    "ext/extended_dynamic_state3",
+    "ext/full_screen_exclusive",
+    "ext/hdr_metadata",
+    "ext/headless_surface",
+    "ext/host_image_copy",
+    "ext/image_compression_control",
+    "ext/image_drm_format_modifier",
+    "ext/mesh_shader",
+    "ext/metal_surface",
+    "ext/pipeline_properties",
+    "ext/private_data",
+    "ext/sample_locations",
+    "ext/shader_object",
+    "ext/swapchain_maintenance1",
+    "ext/tooling_info",
+    "ext/vertex_input_dynamic_state",
+    "google/display_timing",
+    "khr/acceleration_structure",
+    "khr/android_surface",
+    "khr/buffer_device_address",
+    "khr/calibrated_timestamps",
+    "khr/cooperative_matrix",
+    "khr/copy_commands2",
+    "khr/create_renderpass2",
+    "khr/deferred_host_operations",
+    "khr/device_group",
+    "khr/device_group_creation",
+    "khr/display",
+    "khr/display_swapchain",
+    "khr/draw_indirect_count",
+    "khr/dynamic_rendering",
+    "khr/dynamic_rendering_local_read",
+    "khr/external_fence_fd",
+    "khr/external_fence_win32",
+    "khr/external_memory_fd",
+    "khr/external_memory_win32",
+    "khr/external_semaphore_fd",
+    "khr/external_semaphore_win32",
+    "khr/get_memory_requirements2",
+    "khr/get_physical_device_properties2",
+    "khr/get_surface_capabilities2",
+    "khr/line_rasterization",
+    "khr/maintenance1",
+    "khr/maintenance3",
+    "khr/maintenance4",
+    "khr/maintenance5",
+    "khr/maintenance6",
+    "khr/performance_query",
+    "khr/pipeline_executable_properties",
+    "khr/present_wait",
+    "khr/push_descriptor",
+    "khr/ray_tracing_maintenance1",
+    "khr/ray_tracing_pipeline",
+    "khr/sampler_ycbcr_conversion",
+    "khr/surface",
+    "khr/swapchain",
+    "khr/synchronization2",
+    "khr/timeline_semaphore",
+    "khr/wayland_surface",
+    "khr/win32_surface",
+    "khr/xcb_surface",
+    "khr/xlib_surface",
+    "mvk/ios_surface",
+    "mvk/macos_surface",
+    "nn/vi_surface",
+    "nv/copy_memory_indirect",
+    "nv/coverage_reduction_mode",
+    "nv/cuda_kernel_launch",
+    "nv/device_diagnostic_checkpoints",
+    "nv/device_generated_commands_compute",
+    "nv/low_latency2",
+    "nv/memory_decompression",
+    "nv/mesh_shader",
+    "nv/ray_tracing",
+];
+
+/// Core commands whose ash method is not named after the command.
+const ASH_RENAMED_METHODS: &[(&str, &str)] = &[("vkEnumerateInstanceVersion", "try_enumerate_instance_version")];
+
+pub(crate) enum AshBinding {
+    /// A method of a wrapper struct such as `ash::Device` or `ash::khr::swapchain::Device`.
+    Method { path: String, method: String },
+    /// A field of a function pointer table such as `ash::amd::draw_indirect_count::DeviceFn`,
+    /// for extensions without a hand-written wrapper.
+    FnTable { path: String, field: String },
+}
+
+impl AshBinding {
+    pub fn rust_path(&self) -> String {
+        match self {
+            AshBinding::Method { path, method } => format!("{path}::{method}"),
+            AshBinding::FnTable { path, field } => format!("{path}::{field}"),
+        }
+    }
+
+    pub fn docs_url(&self) -> String {
+        let (path, anchor) = match self {
+            AshBinding::Method { path, method } => (path, format!("method.{method}")),
+            AshBinding::FnTable { path, field } => (path, format!("structfield.{field}")),
+        };
+        let (module, ty) = path.rsplit_once("::").unwrap();
+        let module = module.strip_prefix("ash").unwrap().replace("::", "/");
+        format!("{ASH_DOCS}{module}/struct.{ty}.html#{anchor}")
+    }
+}
+
+impl Converter {
+    /// `None` for commands that ash does not expose, such as those of disabled extensions.
+    pub(crate) fn ash_binding(&self, name: &str) -> Option<AshBinding> {
+        let level = self.dispatch_level(self.command_definition(name));
+        let parents = self.parents.get(name).map(String::as_str).unwrap_or("");
+        if parents.split(", ").any(|parent| parent.starts_with("VK_VERSION_")) {
+            let ty = match level {
+                DispatchLevel::Global => "Entry",
+                DispatchLevel::Instance => "Instance",
+                DispatchLevel::Device => "Device",
+            };
+            let method = ASH_RENAMED_METHODS
+                .iter()
+                .find(|(command, _)| *command == name)
+                .map(|(_, method)| method.to_string())
+                .unwrap_or_else(|| name.strip_prefix("vk").unwrap().to_snake_case());
+            return Some(AshBinding::Method {
+                path: format!("ash::{ty}"),
+                method,
+            });
+        }
+
+        let modules = parents
+            .split(", ")
+            .filter(|parent| self.extensions.contains_key(*parent))
+            .map(extension_module)
+            .collect::<Vec<_>>();
+        let wrapped = modules
+            .iter()
+            .find(|(vendor, extension)| ASH_EXTENSION_WRAPPERS.contains(&format!("{vendor}/{extension}").as_str()));
+        let level = match level {
+            DispatchLevel::Device => "Device",
+            _ => "Instance",
+        };
+        let field = name.strip_prefix("vk").unwrap().to_snake_case();
+        if let Some((vendor, extension)) = wrapped {
+            let suffix = format!("_{vendor}");
+            return Some(AshBinding::Method {
+                path: format!("ash::{vendor}::{extension}::{level}"),
+                method: field.strip_suffix(&suffix).unwrap_or(&field).to_string(),
+            });
+        }
+        let (vendor, extension) = modules.first()?;
+        Some(AshBinding::FnTable {
+            path: format!("ash::{vendor}::{extension}::{level}Fn"),
+            field,
+        })
+    }
+
+    /// The declaration of the ash method or function pointer field exposing a command.
+    ///
+    /// Wrapper signatures are derived from the parameters following the conventions of ash's
+    /// hand-written wrappers: the handle the wrapper holds is taken from `&self`, count and array
+    /// pairs become slices, optional pointers become `Option<&T>` and the trailing output parameter
+    /// becomes the return value. The output array of `vkAllocate*` and `vkCreate*Pipelines` is
+    /// returned as a `Vec`, with the pipelines created so far kept in the error of the latter.
+    pub(crate) fn ash_signature(&self, name: &str) -> Option<String> {
+        let (path, method) = match self.ash_binding(name)? {
+            AshBinding::FnTable { path, field } => {
+                // ash declares no PFN types for aliases, the field uses the aliased command's one.
+                let pfn = &self.command_definition(name).proto.name;
+                return Some(format!("// {path}\npub {field}: PFN_{pfn},"));
+            }
+            AshBinding::Method { path, method } => (path, method),
+        };
+        let command = self.command_definition(name);
+        let params = command
+            .params
+            .iter()
+            .filter(|param| param.api.as_ref().map(String::as_str) != Some("vulkansc"))
+            .collect::<Vec<_>>();
+        let is_count = |name: &str| params.iter().any(|param| param.len.as_deref() == Some(name));
+        // `ash::Device` and the device level extension wrappers hold the `VkDevice`, and their
+        // instance level counterparts the `VkInstance`. Other handles are still passed.
+        let held_handle = match path.rsplit("::").next() {
+            Some("Device") => Some("VkDevice"),
+            Some("Instance") => Some("VkInstance"),
+            _ => None,
+        };
+
+        let mut rs_params = vec!["&self".to_string()];
+        let mut output = None;
+        for (i, param) in params.iter().enumerate() {
+            let decl = c_decl::parse_declaration(&param.definition.code).unwrap();
+            let param_name = param.definition.name.as_str();
+            if i == 0 && held_handle == Some(decl.ty.base_name()) {
+                continue;
+            }
+            if is_count(param_name) {
+                continue;
+            }
+            let c_decl::CType::Pointer { pointee, .. } = &decl.ty else {
+                rs_params.push(format!("{}: {}", decl.rust_name(), self.ash_value_type(&decl.ty)));
+                continue;
+            };
+            let rs_name = ash_param_name(param_name);
+            let element = self.ash_value_type(pointee);
+            let optional = param.optional.as_deref().map_or(false, |optional| optional.starts_with("true"));
+            let is_last = i == params.len() - 1;
+            if pointee.is_void() {
+                let pointer = if pointee.is_const() { "*const" } else { "*mut" };
+                rs_params.push(format!("{rs_name}: {pointer} c_void"));
+            } else if pointee.is_const() {
+                let ty = match param.len.as_deref() {
+                    Some("null-terminated") => "&CStr".to_string(),
+                    Some(_) => format!("&[{element}]"),
+                    None => format!("&{element}"),
+                };
+                if optional {
+                    rs_params.push(format!("{rs_name}: Option<{ty}>"));
+                } else {
+                    rs_params.push(format!("{rs_name}: {ty}"));
+                }
+            } else if is_last && param.len.as_ref().map_or(false, |len| {
+                len.contains("->") || params.iter().any(|count| count.definition.name == *len)
+            }) {
+                output = Some(format!("Vec<{element}>"));
+            } else if param.len.is_some() {
+                rs_params.push(format!("{rs_name}: &mut [{element}]"));
+            } else if is_last && !self.has_s_type(pointee.base_name()) {
+                output = Some(element);
+            } else {
+                rs_params.push(format!("{rs_name}: &mut {element}"));
+            }
+        }
+
+        let return_type = command.proto.type_name.as_deref().unwrap_or("void");
+        let return_type = match (return_type, output) {
+            ("VkResult", Some(output)) if method.starts_with("create_") && method.ends_with("_pipelines") => {
+                format!(" -> Result<{output}, ({output}, vk::Result)>")
+            }
+            ("VkResult", output) => format!(" -> VkResult<{}>", output.unwrap_or_else(|| "()".to_string())),
+            ("void", Some(output)) => format!(" -> {output}"),
+            ("void", None) => String::new(),
+            ("VkBool32", _) => " -> bool".to_string(),
+            (return_type, _) => format!(" -> {}", self.ash_value_type(&c_decl::parse_type(return_type).unwrap())),
+        };
+        let rs_params = rs_params
+            .iter()
+            .fold(String::new(), |a, b| a + "    " + b + ",\n");
+        Some(format!(
+            "// {path}
+pub unsafe fn {method}(
+{rs_params}){return_type};"
+        ))
+    }
+
+    /// A Rust type in ash's wrapper signatures, with `'_` on structs that carry a lifetime.
+    fn ash_value_type(&self, ty: &c_decl::CType) -> String {
+        let rs_type = ty.to_rust();
+        match ty {
+            c_decl::CType::Named { name, .. } if self.ash.has_lifetime(name) => rs_type + "<'_>",
+            _ => rs_type,
+        }
+    }
+
+    pub(crate) fn has_s_type(&self, name: &str) -> bool {
+        let Some(vk_parse::TypeSpec::Members(members)) = self.types.get(name).map(|ty| &ty.spec) else {
+            return false;
+        };
+        members.iter().any(|member| match member {
+            vk_parse::TypeMember::Definition(def) => def
+                .markup
+                .iter()
+                .any(|markup| matches!(markup, vk_parse::TypeMemberMarkup::Name(name) if name == "sType")),
+            _ => false,
+        })
+    }
+
+    pub(crate) fn ash_attributes(&self, name: &str) -> String {
+        let Some(binding) = self.ash_binding(name) else {
+            return String::new();
+        };
+        format!(
+            "ash: {}\nash_docs: {}\n",
+            crate::json_string(&binding.rust_path()),
+            crate::json_string(&binding.docs_url())
+        )
+    }
+}
+
+/// The `vendor` and `name` modules ash declares an extension in, e.g. `khr` and `swapchain`.
+fn extension_module(extension: &str) -> (String, String) {
+    let (vendor, name) = extension.strip_prefix("VK_").unwrap().split_once('_').unwrap();
+    let name = if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{name}")
+    } else {
+        name.to_string()
+    };
+    (vendor.to_lowercase(), name)
+}
+
+/// ash drops the Hungarian `p`/`pp` prefix and calls the allocator `allocation_callbacks`.
+fn ash_param_name(name: &str) -> String {
+    if name == "pAllocator" {
+        return "allocation_callbacks".to_string();
+    }
+    let name = name.to_snake_case();
+    let name = name
+        .strip_prefix("pp_")
+        .or_else(|| name.strip_prefix("p_"))
+        .unwrap_or(&name);
+    c_decl::rust_param_name(name)
+}
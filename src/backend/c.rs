@@ -0,0 +1,167 @@
+use regex::Regex;
+
+use super::{CodeBlock, LanguageBackend};
+use crate::Converter;
+
+/// The declarations exactly as they appear in the Vulkan headers.
+pub(crate) struct CBackend;
+
+impl LanguageBackend for CBackend {
+    fn struct_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let ty = &converter.types[name];
+        if let Some(alias) = &ty.alias {
+            return vec![c_block(format!("typedef {alias} {name};"))];
+        }
+        let category = ty.category.as_ref().map(String::as_str).unwrap_or("struct");
+        let vk_parse::TypeSpec::Members(members) = &ty.spec else {
+            unimplemented!()
+        };
+        let c_members = members
+            .iter()
+            .filter_map(|member| {
+                let result = match member {
+                    vk_parse::TypeMember::Comment(comment) => format!("// {comment}"),
+                    vk_parse::TypeMember::Definition(def) => {
+                        if let Some(api) = &def.api {
+                            if api != "vulkan" {
+                                return None;
+                            }
+                        }
+                        Regex::new(r" +")
+                            .unwrap()
+                            .replace_all(&def.code, " ")
+                            .to_string()
+                    }
+                    _ => todo!(),
+                };
+                Some(result)
+            })
+            .fold(String::new(), |a, b| a + "    " + &b + ";\n")
+            .trim_end()
+            .to_string();
+        vec![c_block(format!(
+            "typedef {category} {name} {{
+{c_members}
+}} {name};"
+        ))]
+    }
+
+    fn enum_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let Some(e) = converter.enums.get(name) else {
+            let alias = converter.types[name].alias.as_ref().unwrap();
+            return vec![c_block(format!("#define {name} {alias}"))];
+        };
+        let children = e
+            .children
+            .iter()
+            .map(|a| match a {
+                vk_parse::EnumsChild::Enum(d) => match &d.spec {
+                    vk_parse::EnumSpec::None => d.name.clone(),
+                    vk_parse::EnumSpec::Alias { alias, extends } => {
+                        assert!(extends.is_none());
+                        format!("{} = {}", d.name, alias)
+                    }
+                    vk_parse::EnumSpec::Bitpos { bitpos, extends } => {
+                        assert!(extends.is_none());
+                        let value: u64 = 1 << bitpos;
+                        if let Some(bitwidth) = e.bitwidth {
+                            assert_eq!(bitwidth, 64);
+                            format!("{} = {:#010x}ULL", d.name, value)
+                        } else {
+                            format!("{} = {:#010x}", d.name, value)
+                        }
+                    }
+                    vk_parse::EnumSpec::Value { value, extends } => {
+                        assert!(extends.is_none());
+                        format!("{} = {}", d.name, value)
+                    }
+                    _ => todo!(),
+                },
+                vk_parse::EnumsChild::Unused(_) => String::new(),
+                vk_parse::EnumsChild::Comment(comment) => {
+                    format!("// {comment}")
+                }
+                _ => todo!(),
+            })
+            .fold(String::new(), |a, b| a + "    " + &b + ",\n")
+            .trim_end()
+            .to_string();
+        vec![c_block(format!(
+            "typedef enum {name} {{
+{children}
+}} {name};"
+        ))]
+    }
+
+    fn flags_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        type_code_blocks(converter, name)
+    }
+
+    fn handle_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        type_code_blocks(converter, name)
+    }
+
+    fn command_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let command = converter.command_definition(name);
+        let return_type = command.proto.type_name.as_deref().unwrap_or("");
+        let c_params = command
+            .params
+            .iter()
+            .filter(|a| a.api.as_ref().map(String::as_str) != Some("vulkansc"))
+            .map(|a| a.definition.code.as_str())
+            .collect::<Vec<_>>();
+        let params = c_params
+            .iter()
+            .fold(String::new(), |a, b| a + "    " + b + ",\n");
+        let pfn_params = c_params.join(", ");
+        let params = params[..params.len() - 2].to_string();
+        vec![
+            c_block(format!(
+                "{return_type} {name}(
+{params});"
+            )),
+            CodeBlock::new(
+                "c",
+                "C PFN",
+                format!("typedef {return_type} (VKAPI_PTR *PFN_{name})({pfn_params});"),
+            ),
+        ]
+    }
+
+    fn funcpointer_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        type_code_blocks(converter, name)
+    }
+
+    fn constant_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let value = match &converter.consts[name].spec {
+            vk_parse::EnumSpec::Value { value, .. } => value,
+            vk_parse::EnumSpec::Alias { alias, .. } => alias,
+            _ => unimplemented!(),
+        };
+        vec![c_block(format!("#define {name} {value}"))]
+    }
+
+    fn define_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        type_code_blocks(converter, name)
+    }
+
+    fn basetype_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        type_code_blocks(converter, name)
+    }
+}
+
+fn c_block(code: String) -> CodeBlock {
+    CodeBlock::new("c", "C", code)
+}
+
+/// Types whose definition is verbatim C code in vk.xml, or a typedef for aliases.
+fn type_code_blocks(converter: &Converter, name: &str) -> Vec<CodeBlock> {
+    let ty = &converter.types[name];
+    if let Some(alias) = &ty.alias {
+        return vec![c_block(format!("typedef {alias} {name};"))];
+    }
+    match &ty.spec {
+        vk_parse::TypeSpec::Code(code) => vec![c_block(code.code.clone())],
+        _ => unimplemented!(),
+    }
+}
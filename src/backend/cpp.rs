@@ -0,0 +1,520 @@
+use super::{extending_structs, lower_first, registry_tags, tagged_camel_case, CodeBlock, LanguageBackend};
+use crate::{c_decl, variant_ident, Converter};
+
+/// The `vk` namespace of vulkan.hpp, following the naming rules of its generator.
+pub(crate) struct CppBackend;
+
+/// Base types vulkan.hpp redeclares in the `vk` namespace.
+const CPP_BASETYPES: &[&str] = &["VkBool32", "VkDeviceAddress", "VkDeviceSize", "VkRemoteAddressNV", "VkSampleMask"];
+
+impl LanguageBackend for CppBackend {
+    fn struct_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let ty = &converter.types[name];
+        if let Some(alias) = &ty.alias {
+            return vec![cpp_block(namespace(&format!("using {} = {};", cpp_name(name), vk_name(alias))))];
+        }
+        let is_union = ty.category.as_deref() == Some("union");
+        let vk_parse::TypeSpec::Members(members) = &ty.spec else {
+            unimplemented!()
+        };
+        let cpp_members = members
+            .iter()
+            .filter_map(|member| {
+                let vk_parse::TypeMember::Definition(def) = member else {
+                    return None;
+                };
+                if def.api.as_deref().map_or(false, |api| api != "vulkan") {
+                    return None;
+                }
+                let decl = c_decl::parse_declaration(&def.code).unwrap();
+                let member_name = decl.name.as_deref().unwrap();
+                let cpp_type = cpp_type(&decl.ty);
+                let member = match (decl.bitfield, &def.values) {
+                    (Some(width), _) => format!("{cpp_type} {member_name} : {width};"),
+                    (None, _) if is_union => format!("{cpp_type} {member_name};"),
+                    (None, Some(values)) if member_name == "sType" => format!(
+                        "{cpp_type} {member_name} = vk::StructureType::{};",
+                        cpp_enum_value(converter, "VkStructureType", values)
+                    ),
+                    (None, _) => format!("{cpp_type} {member_name} = {{}};"),
+                };
+                Some(format!("  {member}\n"))
+            })
+            .collect::<String>();
+        let keyword = if is_union { "union" } else { "struct" };
+        let mut blocks = vec![cpp_block(namespace(&format!(
+            "{keyword} {}\n{{\n{cpp_members}}};",
+            cpp_name(name)
+        )))];
+        if let Some(chain) = structure_chain(converter, name) {
+            blocks.push(CodeBlock::new("cpp", "C++ (StructureChain)", chain));
+        }
+        blocks
+    }
+
+    fn enum_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let Some(e) = converter.enums.get(name) else {
+            let alias = converter.types[name].alias.as_ref().unwrap();
+            return vec![cpp_block(namespace(&format!("using {} = {};", cpp_name(name), vk_name(alias))))];
+        };
+        if e.kind.as_deref() == Some("bitmask") {
+            let base = if e.bitwidth == Some(64) { "VkFlags64" } else { "VkFlags" };
+            return vec![cpp_block(namespace(&flag_bits_enum(converter, name, base)))];
+        }
+        let values = converter
+            .enum_values(name)
+            .iter()
+            .map(|value| format!("  {} = {},\n", cpp_enum_value(converter, name, &value.name), value.name))
+            .collect::<String>();
+        vec![cpp_block(namespace(&format!(
+            "enum class {}\n{{\n{}}};",
+            cpp_name(name),
+            values.trim_end_matches(",\n").to_string() + "\n"
+        )))]
+    }
+
+    fn flags_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        if let Some(alias) = &converter.types[name].alias {
+            return vec![cpp_block(namespace(&format!("using {} = {};", cpp_name(name), vk_name(alias))))];
+        }
+        let base = if converter.flags_bitwidth(name) == 64 { "VkFlags64" } else { "VkFlags" };
+        // vulkan.hpp declares an empty FlagBits enum for flags that have no bits yet.
+        let bits_name = converter
+            .flag_bits_type(name)
+            .map(str::to_string)
+            .unwrap_or_else(|| name.replacen("Flags", "FlagBits", 1));
+        let bits_enum = if converter.enums.contains_key(&bits_name) {
+            flag_bits_enum(converter, &bits_name, base)
+        } else {
+            format!("enum class {} : {base}\n{{\n}};", cpp_name(&bits_name))
+        };
+        vec![cpp_block(namespace(&format!(
+            "{bits_enum}\n\nusing {} = vk::Flags<{}>;",
+            cpp_name(name),
+            cpp_name(&bits_name)
+        )))]
+    }
+
+    fn handle_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let ty = &converter.types[name];
+        if let Some(alias) = &ty.alias {
+            return vec![cpp_block(namespace(&format!("using {} = {};", cpp_name(name), vk_name(alias))))];
+        }
+        let cpp_name = cpp_name(name);
+        let object_type = cpp_enum_value(converter, "VkObjectType", ty.objtypeenum.as_ref().unwrap());
+        let member = format!("m_{}", lower_first(cpp_name));
+        vec![cpp_block(namespace(&format!(
+            "class {cpp_name}
+{{
+public:
+  using CType      = {name};
+  using NativeType = {name};
+
+  static constexpr vk::ObjectType objectType = vk::ObjectType::{object_type};
+
+  {cpp_name}() = default;
+  {cpp_name}( {name} {} ) noexcept : {member}( {} ) {{}}
+
+  operator {name}() const noexcept {{ return {member}; }}
+  explicit operator bool() const noexcept {{ return {member} != VK_NULL_HANDLE; }}
+
+private:
+  {name} {member} = {{}};
+}};",
+            lower_first(cpp_name),
+            lower_first(cpp_name),
+        )))]
+    }
+
+    fn command_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        vec![cpp_block(command_signature(converter, name))]
+    }
+
+    fn constant_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let (target, _) = converter.evaluate_constant(name);
+        let cpp_type = match target.name.as_str() {
+            "VK_TRUE" | "VK_FALSE" => "vk::Bool32",
+            _ => target.type_suffix.as_deref().unwrap(),
+        };
+        let cpp_constant = tagged_camel_case(converter, name.strip_prefix("VK_").unwrap());
+        vec![cpp_block(namespace(&format!(
+            "constexpr {cpp_type} {cpp_constant} = {name};"
+        )))]
+    }
+
+    fn define_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let code = match name {
+            "VK_HEADER_VERSION" => "constexpr uint32_t HeaderVersion = VK_HEADER_VERSION;".to_string(),
+            "VK_HEADER_VERSION_COMPLETE" => {
+                "constexpr uint32_t HeaderVersionComplete = VK_HEADER_VERSION_COMPLETE;".to_string()
+            }
+            "VK_MAKE_API_VERSION" => "template <typename T>
+constexpr uint32_t makeApiVersion( T const variant, T const major, T const minor, T const patch );"
+                .to_string(),
+            "VK_MAKE_VERSION" => "template <typename T>
+constexpr uint32_t makeVersion( T const major, T const minor, T const patch );"
+                .to_string(),
+            _ if converter.define_api_version(name).is_some() && name.starts_with("VK_API_VERSION_") => {
+                let version = name.strip_prefix("VK_API_VERSION_").unwrap().replace('_', "");
+                format!("constexpr uint32_t ApiVersion{version} = {name};")
+            }
+            _ => {
+                let function = match name {
+                    "VK_API_VERSION_VARIANT" => "apiVersionVariant",
+                    "VK_API_VERSION_MAJOR" => "apiVersionMajor",
+                    "VK_API_VERSION_MINOR" => "apiVersionMinor",
+                    "VK_API_VERSION_PATCH" => "apiVersionPatch",
+                    "VK_VERSION_MAJOR" => "versionMajor",
+                    "VK_VERSION_MINOR" => "versionMinor",
+                    "VK_VERSION_PATCH" => "versionPatch",
+                    _ => return Vec::new(),
+                };
+                format!("template <typename T>\nconstexpr uint32_t {function}( T const version );")
+            }
+        };
+        vec![cpp_block(namespace(&code))]
+    }
+
+    fn basetype_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        if !CPP_BASETYPES.contains(&name) {
+            return Vec::new();
+        }
+        let vk_parse::TypeSpec::Code(code) = &converter.types[name].spec else {
+            unimplemented!()
+        };
+        let decl = c_decl::parse_declaration(&code.code).unwrap();
+        vec![cpp_block(namespace(&format!(
+            "using {} = {};",
+            cpp_name(name),
+            cpp_type(&decl.ty)
+        )))]
+    }
+}
+
+fn cpp_block(code: String) -> CodeBlock {
+    CodeBlock::new("cpp", "C++", code)
+}
+
+/// Wraps declarations in the `vk` namespace, indented the way vulkan.hpp is formatted.
+fn namespace(code: &str) -> String {
+    let code = code
+        .lines()
+        .map(|line| if line.is_empty() { String::new() } else { format!("  {line}") })
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("namespace vk\n{{\n{code}\n}}")
+}
+
+fn cpp_name(name: &str) -> &str {
+    name.strip_prefix("Vk").unwrap_or(name)
+}
+
+fn vk_name(name: &str) -> String {
+    match name.strip_prefix("Vk") {
+        Some(name) => format!("vk::{name}"),
+        None => name.to_string(),
+    }
+}
+
+fn cpp_type(ty: &c_decl::CType) -> String {
+    match ty {
+        c_decl::CType::Named { name, is_const: true } => format!("const {}", vk_name(name)),
+        c_decl::CType::Named { name, .. } => vk_name(name),
+        c_decl::CType::Pointer { pointee, is_const: true } => format!("{} * const", cpp_type(pointee)),
+        c_decl::CType::Pointer { pointee, .. } => format!("{} *", cpp_type(pointee)),
+        c_decl::CType::Array { element, len } => match element.as_ref() {
+            c_decl::CType::Array {
+                element,
+                len: inner_len,
+            } => format!(
+                "vk::ArrayWrapper2D<{}, {}, {}>",
+                cpp_type(element),
+                array_len(len),
+                array_len(inner_len)
+            ),
+            element => format!("vk::ArrayWrapper1D<{}, {}>", cpp_type(element), array_len(len)),
+        },
+        c_decl::CType::Function { .. } => unimplemented!(),
+    }
+}
+
+fn array_len(len: &c_decl::ArrayLen) -> String {
+    match len {
+        c_decl::ArrayLen::Literal(len) => len.to_string(),
+        c_decl::ArrayLen::Constant(name) => name.clone(),
+    }
+}
+
+/// The `enum class` of a `FlagBits` enum, with the bits added by features and extensions.
+fn flag_bits_enum(converter: &Converter, bits_name: &str, base: &str) -> String {
+    let bits = converter
+        .enum_values(bits_name)
+        .iter()
+        .map(|bit| format!("  {} = {},\n", cpp_enum_value(converter, bits_name, &bit.name), bit.name))
+        .collect::<String>();
+    format!(
+        "enum class {} : {base}\n{{\n{}}};",
+        cpp_name(bits_name),
+        bits.trim_end_matches(",\n").to_string() + "\n"
+    )
+}
+
+/// vulkan.hpp's name for an enum value: the enum prefix and `_BIT` are dropped, the rest is
+/// camel cased behind an `e`, and a vendor tag other than the enum's own is kept in capitals,
+/// e.g. `VK_FORMAT_R8G8B8A8_UNORM` becomes `eR8G8B8A8Unorm`.
+fn cpp_enum_value(converter: &Converter, enum_name: &str, value: &str) -> String {
+    let value = variant_ident(enum_name, value);
+    let value = match value.strip_prefix("TYPE_") {
+        Some(rest) if rest.starts_with(|c: char| c.is_ascii_digit()) => rest.to_string(),
+        _ => value,
+    };
+    format!("e{}", tagged_camel_case(converter, &value))
+}
+
+/// How a struct is used in a `vk::StructureChain`: behind the struct it extends, or as the
+/// head of a chain of the structs extending it.
+fn structure_chain(converter: &Converter, name: &str) -> Option<String> {
+    let cpp_name = cpp_name(name);
+    let variable = lower_first(cpp_name);
+    let parents = converter.types[name]
+        .structextends
+        .as_deref()
+        .map(|extends| extends.split(',').collect::<Vec<_>>())
+        .unwrap_or_default();
+    if let Some(parent) = parents.first() {
+        let extends = parents
+            .iter()
+            .map(|parent| format!("// vk::StructExtends<vk::{cpp_name}, {}>::value == true\n", vk_name(parent)))
+            .collect::<String>();
+        return Some(format!(
+            "{extends}vk::StructureChain<{}, vk::{cpp_name}> chain;
+vk::{cpp_name} & {variable} = chain.get<vk::{cpp_name}>();",
+            vk_name(parent)
+        ));
+    }
+    let child = *extending_structs(converter, name).first()?;
+    Some(format!(
+        "vk::StructureChain<vk::{cpp_name}, {}> chain;
+vk::{cpp_name} & {variable} = chain.get<vk::{cpp_name}>();",
+        vk_name(child)
+    ))
+}
+
+/// The method name of a command, following vulkan.hpp's `generateCommandName`: `vk` is dropped,
+/// then the name of the handle the method is called on, or `Cmd` for command buffers, along
+/// with the handle's vendor tag when the command has the same one.
+fn command_name(converter: &Converter, name: &str, handle: Option<&str>) -> String {
+    let mut command = lower_first(name.strip_prefix("vk").unwrap());
+    let Some(handle) = handle else {
+        return command;
+    };
+    let camel_tag = |name: &str| {
+        registry_tags(converter)
+            .filter(|tag| name.ends_with(tag))
+            .max_by_key(|tag| tag.len())
+            .map(str::to_string)
+    };
+    let argument_tag = camel_tag(handle);
+    let handle = handle.strip_prefix("Vk").unwrap_or(handle);
+    let mut search = argument_tag
+        .as_deref()
+        .and_then(|tag| handle.strip_suffix(tag))
+        .unwrap_or(handle)
+        .to_string();
+    let mut pos = command.find(&search);
+    if pos.is_none() {
+        search = lower_first(&search);
+        pos = command.find(&search);
+    }
+    if let Some(pos) = pos {
+        // A plural of the handle name is dropped as well.
+        let len = if command[pos..].starts_with(&format!("{search}s")) {
+            search.len() + 1
+        } else {
+            search.len()
+        };
+        command.replace_range(pos..pos + len, "");
+    } else if search == "commandBuffer" && command.starts_with("cmd") {
+        command.replace_range(..3, "");
+        pos = Some(0);
+    }
+    if pos == Some(0) {
+        command = lower_first(&command);
+    }
+    if let Some(tag) = argument_tag.filter(|tag| camel_tag(&command).as_ref() == Some(tag)) {
+        command.truncate(command.len() - tag.len());
+    }
+    command
+}
+
+/// vulkan.hpp drops the Hungarian `p`/`pp` prefix of parameters.
+fn cpp_param_name(name: &str) -> String {
+    let stripped = name
+        .strip_prefix("pp")
+        .or_else(|| name.strip_prefix('p'))
+        .filter(|rest| rest.starts_with(|c: char| c.is_ascii_uppercase()));
+    match stripped {
+        Some(rest) => lower_first(rest),
+        None => name.to_string(),
+    }
+}
+
+/// The enhanced-mode declaration vulkan.hpp generates for a command: a method of the class of
+/// the dispatchable handle, with count and array pairs as `vk::ArrayProxy`, optional pointers as
+/// `vk::Optional` and the trailing output parameter as the return value.
+fn command_signature(converter: &Converter, name: &str) -> String {
+    let command = converter.command_definition(name);
+    let params = command
+        .params
+        .iter()
+        .filter(|param| param.api.as_ref().map(String::as_str) != Some("vulkansc"))
+        .collect::<Vec<_>>();
+    let is_count = |name: &str| params.iter().any(|param| param.len.as_deref() == Some(name));
+    let is_count_pointer = |len: &str| {
+        len.contains("->")
+            || params
+                .iter()
+                .any(|count| count.definition.name == len && count.definition.code.contains('*'))
+    };
+
+    let mut class = None;
+    let mut handle = None;
+    let mut templates = Vec::new();
+    let mut cpp_params: Vec<(String, Option<&str>)> = Vec::new();
+    let mut output = None;
+    let mut returns_vector = false;
+    for (i, param) in params.iter().enumerate() {
+        let decl = c_decl::parse_declaration(&param.definition.code).unwrap();
+        let param_name = param.definition.name.as_str();
+        if i == 0 && converter.is_dispatchable_handle(decl.ty.base_name()) {
+            class = Some(vk_name(decl.ty.base_name()));
+            handle = Some(decl.ty.base_name().to_string());
+            continue;
+        }
+        if is_count(param_name) {
+            continue;
+        }
+        let c_decl::CType::Pointer { pointee, .. } = &decl.ty else {
+            cpp_params.push((format!("{} {param_name}", cpp_type(&decl.ty)), None));
+            continue;
+        };
+        let cpp_name = cpp_param_name(param_name);
+        let optional = param.optional.as_deref().map_or(false, |optional| optional.starts_with("true"));
+        let is_last = i == params.len() - 1;
+        let element = if pointee.is_void() {
+            if !templates.contains(&"DataType") {
+                templates.push("DataType");
+            }
+            "DataType".to_string()
+        } else {
+            cpp_type(&strip_const(pointee))
+        };
+        if pointee.is_const() {
+            match param.len.as_deref() {
+                Some("null-terminated") => cpp_params.push((format!("const std::string & {cpp_name}"), None)),
+                Some(_) => cpp_params.push((format!("vk::ArrayProxy<const {element}> const & {cpp_name}"), None)),
+                None if optional => {
+                    cpp_params.push((format!("vk::Optional<const {element}> {cpp_name}"), Some("nullptr")))
+                }
+                None => cpp_params.push((format!("const {element} & {cpp_name}"), None)),
+            }
+        } else if is_last && param.len.as_deref().map_or(false, |len| is_count_pointer(len)) {
+            output = Some(format!("std::vector<{element}>"));
+            returns_vector = true;
+        } else if param.len.is_some() {
+            cpp_params.push((format!("vk::ArrayProxy<{element}> const & {cpp_name}"), None));
+        } else if is_last {
+            output = Some(element);
+        } else {
+            cpp_params.push((format!("{element} & {cpp_name}"), None));
+        }
+    }
+    cpp_params.push((
+        "Dispatch const & d".to_string(),
+        Some("VULKAN_HPP_DEFAULT_DISPATCHER"),
+    ));
+    // Default arguments are only kept for the trailing parameters.
+    let first_default = cpp_params
+        .iter()
+        .rposition(|(_, default)| default.is_none())
+        .map_or(0, |i| i + 1);
+    let cpp_params = cpp_params
+        .iter()
+        .enumerate()
+        .map(|(i, (param, default))| match default {
+            Some(default) if i >= first_default => format!("  {param} = {default}"),
+            _ => format!("  {param}"),
+        })
+        .collect::<Vec<_>>()
+        .join(",\n");
+
+    let return_type = match command.proto.type_name.as_deref().unwrap_or("void") {
+        "VkResult" => {
+            let extra_codes = command
+                .successcodes
+                .iter()
+                .flat_map(|codes| codes.split(','))
+                .filter(|code| *code != "VK_SUCCESS" && !(returns_vector && *code == "VK_INCOMPLETE"))
+                .count();
+            match output.as_ref() {
+                Some(output) if extra_codes > 0 => format!("vk::ResultValue<{output}>"),
+                None if extra_codes > 0 => "vk::Result".to_string(),
+                output => format!(
+                    "typename vk::ResultValueType<{}>::type",
+                    output.map(String::as_str).unwrap_or("void")
+                ),
+            }
+        }
+        "void" => output.clone().unwrap_or_else(|| "void".to_string()),
+        return_type => cpp_type(&c_decl::parse_type(return_type).unwrap()),
+    };
+
+    let method = command_name(converter, name, handle.as_deref());
+    let (qualified, constness) = match &class {
+        Some(class) => (format!("{class}::{method}"), " const"),
+        None => (format!("vk::{method}"), ""),
+    };
+    let template_params = templates
+        .iter()
+        .map(|ty| format!("typename {ty}, "))
+        .collect::<String>();
+    let mut signature = format!(
+        "template <{template_params}typename Dispatch = VULKAN_HPP_DEFAULT_DISPATCHER_TYPE>
+{return_type} {qualified}(
+{cpp_params}){constness};"
+    );
+
+    // Extendable output structs can also be returned as a whole structure chain.
+    let output_struct = output
+        .as_deref()
+        .filter(|_| !returns_vector)
+        .and_then(|output| output.strip_prefix("vk::"))
+        .map(|output| format!("Vk{output}"));
+    if let Some(output_struct) = output_struct {
+        if !extending_structs(converter, &output_struct).is_empty() {
+            let return_type = return_type.replace(&vk_name(&output_struct), "vk::StructureChain<X, Y, Z...>");
+            signature += &format!(
+                "
+
+template <typename X, typename Y, typename... Z, {template_params}typename Dispatch = VULKAN_HPP_DEFAULT_DISPATCHER_TYPE>
+{return_type} {qualified}(
+{cpp_params}){constness};"
+            );
+        }
+    }
+    signature
+}
+
+fn strip_const(ty: &c_decl::CType) -> c_decl::CType {
+    match ty {
+        c_decl::CType::Named { name, .. } => c_decl::CType::Named {
+            name: name.clone(),
+            is_const: false,
+        },
+        c_decl::CType::Pointer { pointee, .. } => c_decl::CType::Pointer {
+            pointee: pointee.clone(),
+            is_const: false,
+        },
+        ty => ty.clone(),
+    }
+}
@@ -0,0 +1,208 @@
+//! Language backends producing the code tabs of each page. Every page category has a method
+//! returning the blocks of that language, so adding a binding language only means adding a
+//! backend here.
+
+mod c;
+mod cpp;
+mod rust;
+mod vulkanalia;
+mod xml;
+mod zig;
+
+use crate::Converter;
+
+pub(crate) use c::CBackend;
+pub(crate) use cpp::CppBackend;
+pub(crate) use rust::RustBackend;
+pub(crate) use vulkanalia::VulkanaliaBackend;
+pub(crate) use xml::XmlBackend;
+pub(crate) use zig::ZigBackend;
+
+/// One tab of a `::code-group`.
+pub(crate) struct CodeBlock {
+    /// Language of the fenced code block, used for highlighting.
+    pub lang: &'static str,
+    /// Label of the tab, e.g. `Rust (builder)`.
+    pub label: String,
+    pub code: String,
+}
+
+impl CodeBlock {
+    pub fn new(lang: &'static str, label: impl Into<String>, code: impl Into<String>) -> Self {
+        Self {
+            lang,
+            label: label.into(),
+            code: code.into(),
+        }
+    }
+}
+
+/// Every method defaults to no tabs, so backends only implement the categories they support.
+#[allow(unused_variables)]
+pub(crate) trait LanguageBackend {
+    /// Structs and unions, including their aliases.
+    fn struct_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        Vec::new()
+    }
+
+    /// Enum definitions and enum aliases.
+    fn enum_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        Vec::new()
+    }
+
+    fn flags_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        Vec::new()
+    }
+
+    fn handle_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        Vec::new()
+    }
+
+    fn command_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        Vec::new()
+    }
+
+    fn funcpointer_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        Vec::new()
+    }
+
+    /// API constants such as `VK_MAX_EXTENSION_NAME_SIZE`.
+    fn constant_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        Vec::new()
+    }
+
+    fn define_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        Vec::new()
+    }
+
+    fn basetype_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        Vec::new()
+    }
+
+    /// Extension pages, in place of the extension metadata include.
+    fn extension_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        Vec::new()
+    }
+}
+
+/// The backends named in the comma separated `VK_DOC_TABS` environment variable, in tab order.
+/// Defaults to `c,cpp,rust,zig`.
+pub(crate) fn configured_backends() -> Vec<Box<dyn LanguageBackend>> {
+    let tabs = std::env::var("VK_DOC_TABS").unwrap_or_else(|_| "c,cpp,rust,zig".to_string());
+    tabs.split(',')
+        .map(|tab| -> Box<dyn LanguageBackend> {
+            match tab.trim() {
+                "c" => Box::new(CBackend),
+                "cpp" => Box::new(CppBackend),
+                "rust" => Box::new(RustBackend),
+                "vulkanalia" => Box::new(VulkanaliaBackend),
+                "xml" => Box::new(XmlBackend::load()),
+                "zig" => Box::new(ZigBackend),
+                tab => panic!("unknown code tab backend {tab}"),
+            }
+        })
+        .collect()
+}
+
+/// Renders blocks as a `::code-group`, or as a plain code block when there is only one.
+pub(crate) fn code_group(blocks: &[CodeBlock]) -> String {
+    match blocks {
+        [] => String::new(),
+        [block] => format!("```{}\n{}\n```", block.lang, block.code),
+        blocks => {
+            let tabs = blocks
+                .iter()
+                .map(|block| format!("```{} [{}]\n{}\n```\n", block.lang, block.label, block.code))
+                .collect::<String>();
+            format!("::code-group\n{tabs}::")
+        }
+    }
+}
+
+impl Converter {
+    /// Collects the blocks of every configured backend for a page.
+    pub(crate) fn render_tabs(&self, blocks: impl Fn(&dyn LanguageBackend) -> Vec<CodeBlock>) -> String {
+        let blocks = self
+            .backends
+            .iter()
+            .flat_map(|backend| blocks(backend.as_ref()))
+            .collect::<Vec<_>>();
+        self.record_code_links(&blocks);
+        code_group(&blocks)
+    }
+}
+
+/// The vendor tag, such as `KHR`, that an upper snake case name ends with.
+fn vendor_tag<'a>(converter: &'a Converter, name: &str) -> Option<&'a str> {
+    registry_tags(converter)
+        .filter(|tag| name.ends_with(&format!("_{tag}")))
+        .max_by_key(|tag| tag.len())
+}
+
+/// The vendor tags of the registry, such as `KHR` or `EXT`.
+fn registry_tags(converter: &Converter) -> impl Iterator<Item = &str> {
+    converter
+        .registry
+        .0
+        .iter()
+        .filter_map(|child| match child {
+            vk_parse::RegistryChild::Tags(tags) => Some(tags.children.iter()),
+            _ => None,
+        })
+        .flatten()
+        .map(|tag| tag.name.as_str())
+}
+
+/// Camel cases an upper snake case name, keeping a trailing vendor tag such as `KHR` as is.
+fn tagged_camel_case(converter: &Converter, name: &str) -> String {
+    match vendor_tag(converter, name) {
+        Some(tag) => camel_case(&name[..name.len() - tag.len() - 1]) + tag,
+        None => camel_case(name),
+    }
+}
+
+/// The `toCamelCase` of vulkan.hpp's generator: characters following an underscore or a digit
+/// keep their case, all others are lowercased.
+fn camel_case(name: &str) -> String {
+    let mut result = String::new();
+    let mut keep_upper = true;
+    for c in name.chars() {
+        if c == '_' {
+            keep_upper = true;
+        } else if c.is_ascii_digit() {
+            keep_upper = true;
+            result.push(c);
+        } else if keep_upper {
+            result.push(c);
+            keep_upper = false;
+        } else {
+            result.push(c.to_ascii_lowercase());
+        }
+    }
+    result
+}
+
+fn lower_first(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// Names of the non-alias structs listing `name` in their `structextends`.
+pub(crate) fn extending_structs<'a>(converter: &'a Converter, name: &str) -> Vec<&'a str> {
+    let mut structs = converter
+        .types
+        .iter()
+        .filter(|(_, ty)| ty.alias.is_none())
+        .filter(|(_, ty)| {
+            ty.structextends
+                .as_deref()
+                .map_or(false, |extends| extends.split(',').any(|parent| parent == name))
+        })
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+    structs.sort();
+    structs
+}
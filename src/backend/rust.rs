@@ -0,0 +1,136 @@
+use super::{CodeBlock, LanguageBackend};
+use crate::{ash_constant_type, ash_gen, constants, variant_ident, Converter};
+
+/// ash's `vk` module, produced through ash's own generator.
+pub(crate) struct RustBackend;
+
+impl LanguageBackend for RustBackend {
+    fn struct_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let ty = &converter.types[name];
+        if let Some(alias) = &ty.alias {
+            // ash carries the lifetime of the aliased struct over to the alias.
+            let lifetime = if converter.ash.has_lifetime(alias) { "<'a>" } else { "" };
+            return vec![rs_block(format!(
+                "pub type {}{lifetime} = vk::{}{lifetime};",
+                rs_name(name),
+                rs_name(alias)
+            ))];
+        }
+        let (rs_builder, rs_definition): (Vec<_>, Vec<_>) = converter
+            .ash
+            .struct_items(ty)
+            .into_iter()
+            .partition(ash_gen::is_builder_item);
+        let mut blocks = vec![rs_block(ash_gen::unparse(rs_definition))];
+        if !rs_builder.is_empty() {
+            blocks.push(CodeBlock::new("rs", "Rust (builder)", ash_gen::unparse(rs_builder)));
+        }
+        blocks
+    }
+
+    fn enum_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let Some(e) = converter.enums.get(name) else {
+            let alias = converter.types[name].alias.as_ref().unwrap();
+            return vec![rs_block(format!("pub type {} = vk::{};", rs_name(name), rs_name(alias)))];
+        };
+        vec![rs_block(ash_gen::unparse(converter.ash.enum_items(e)))]
+    }
+
+    fn flags_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        if let Some(alias) = &converter.types[name].alias {
+            return vec![rs_block(format!("pub type {} = vk::{};", rs_name(name), rs_name(alias)))];
+        }
+        vec![rs_block(ash_gen::unparse(converter.ash_flags_items(name)))]
+    }
+
+    fn handle_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let ty = &converter.types[name];
+        if let Some(alias) = &ty.alias {
+            return vec![rs_block(format!("pub type {} = vk::{};", rs_name(name), rs_name(alias)))];
+        }
+        let rs_name = rs_name(name);
+        let object_type = variant_ident("VkObjectType", ty.objtypeenum.as_ref().unwrap());
+        let rs_macro = ash_gen::unparse(converter.ash.handle_items(name));
+        // Expansion of ash's `define_handle!` and `handle_nondispatchable!` macros.
+        let rs_expansion = if converter.is_dispatchable_handle(name) {
+            format!(
+                "#[repr(transparent)]
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash)]
+pub struct {rs_name}(*mut u8);
+impl Handle for {rs_name} {{
+    const TYPE: ObjectType = ObjectType::{object_type};
+    fn as_raw(self) -> u64 {{
+        self.0 as u64
+    }}
+    fn from_raw(x: u64) -> Self {{
+        Self(x as _)
+    }}
+}}
+unsafe impl Send for {rs_name} {{}}
+unsafe impl Sync for {rs_name} {{}}"
+            )
+        } else {
+            format!(
+                "#[repr(transparent)]
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash, Default)]
+pub struct {rs_name}(u64);
+impl Handle for {rs_name} {{
+    const TYPE: ObjectType = ObjectType::{object_type};
+    fn as_raw(self) -> u64 {{
+        self.0
+    }}
+    fn from_raw(x: u64) -> Self {{
+        Self(x)
+    }}
+}}"
+            )
+        };
+        vec![rs_block(format!("{rs_macro}\n// expands to\n{rs_expansion}"))]
+    }
+
+    fn command_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let command = converter.command_definition(name);
+        let mut blocks = vec![rs_block(ash_gen::unparse(converter.ash.command_items(command)))];
+        if let Some(signature) = converter.ash_signature(name) {
+            blocks.push(CodeBlock::new("rs", "Rust (ash)", signature));
+        }
+        blocks
+    }
+
+    fn funcpointer_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        vec![rs_block(ash_gen::unparse(converter.ash.funcptr_items(name)))]
+    }
+
+    fn constant_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let rs_name = &name[3..];
+        let (target, value) = converter.evaluate_constant(name);
+        let rs_type = ash_constant_type(target);
+        let comment = value.hex().map(|hex| format!(" // {hex}")).unwrap_or_default();
+        let rs_value = match &converter.consts[name].spec {
+            vk_parse::EnumSpec::Value { value, extends } => {
+                assert!(extends.is_none());
+                constants::rust_literal(value).unwrap()
+            }
+            vk_parse::EnumSpec::Alias { alias, .. } => format!("vk::{}", &alias[3..]),
+            _ => unimplemented!(),
+        };
+        vec![rs_block(format!("pub const {rs_name}: {rs_type} = {rs_value};{comment}"))]
+    }
+
+    fn define_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        converter.ash_define(name).map(rs_block).into_iter().collect()
+    }
+
+    fn basetype_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let rs_type = converter.basetype_rust(name);
+        vec![rs_block(format!("pub type {} = {rs_type};", rs_name(name)))]
+    }
+}
+
+fn rs_block(code: String) -> CodeBlock {
+    CodeBlock::new("rs", "Rust", code)
+}
+
+fn rs_name(name: &str) -> &str {
+    name.strip_prefix("Vk").unwrap_or(name)
+}
@@ -0,0 +1,391 @@
+use heck::{ToSnakeCase, ToUpperCamelCase};
+
+use super::{extending_structs, CodeBlock, LanguageBackend, RustBackend};
+use crate::{c_decl, variant_ident, Converter, DispatchLevel};
+
+/// The `vk` module of vulkanalia, an alternative to the ash flavour of the Rust tab. Constants,
+/// defines, base types and function pointers are declared the same way by both crates.
+pub(crate) struct VulkanaliaBackend;
+
+impl LanguageBackend for VulkanaliaBackend {
+    fn struct_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let ty = &converter.types[name];
+        if let Some(alias) = &ty.alias {
+            return vec![vulkanalia_block(format!("pub type {} = vk::{};", rs_name(name), rs_name(alias)))];
+        }
+        let keyword = if ty.category.as_deref() == Some("union") { "union" } else { "struct" };
+        let members = struct_members(converter, name);
+        let fields = members
+            .iter()
+            .map(|(_, decl)| format!("    pub {}: {},\n", field_name(decl.name.as_deref().unwrap()), decl.ty.to_rust()))
+            .collect::<String>();
+        let mut blocks = vec![vulkanalia_block(format!(
+            "#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub {keyword} {} {{
+{fields}}}",
+            rs_name(name)
+        ))];
+        if keyword == "struct" {
+            blocks.push(CodeBlock::new("rs", "Rust (vulkanalia builder)", builder(converter, name)));
+        }
+        blocks
+    }
+
+    fn enum_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let Some(e) = converter.enums.get(name) else {
+            let alias = converter.types[name].alias.as_ref().unwrap();
+            return vec![vulkanalia_block(format!("pub type {} = vk::{};", rs_name(name), rs_name(alias)))];
+        };
+        if e.kind.as_deref() == Some("bitmask") {
+            // vulkanalia declares the bits as constants of the `bitflags!` flags type.
+            let Some(flags) = converter.flags_type_of(name) else {
+                return Vec::new();
+            };
+            return vec![vulkanalia_block(bitflags(converter, flags))];
+        }
+        let values = converter
+            .enum_values(name)
+            .iter()
+            .map(|value| {
+                let constant = variant_ident(name, &value.name);
+                match &value.alias {
+                    None => format!("    pub const {constant}: Self = Self({});\n", value.value),
+                    Some(alias) => format!("    pub const {constant}: Self = Self::{};\n", variant_ident(name, alias)),
+                }
+            })
+            .collect::<String>();
+        let rs_name = rs_name(name);
+        vec![vulkanalia_block(format!(
+            "#[repr(transparent)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct {rs_name}(i32);
+
+impl {rs_name} {{
+{values}}}"
+        ))]
+    }
+
+    fn flags_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        if let Some(alias) = &converter.types[name].alias {
+            return vec![vulkanalia_block(format!("pub type {} = vk::{};", rs_name(name), rs_name(alias)))];
+        }
+        vec![vulkanalia_block(bitflags(converter, name))]
+    }
+
+    fn handle_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let ty = &converter.types[name];
+        if let Some(alias) = &ty.alias {
+            return vec![vulkanalia_block(format!("pub type {} = vk::{};", rs_name(name), rs_name(alias)))];
+        }
+        let rs_name = rs_name(name);
+        let repr = if converter.is_dispatchable_handle(name) { "usize" } else { "u64" };
+        let object_type = variant_ident("VkObjectType", ty.objtypeenum.as_ref().unwrap());
+        vec![vulkanalia_block(format!(
+            "#[repr(transparent)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct {rs_name}({repr});
+
+impl Handle for {rs_name} {{
+    type Repr = {repr};
+    const TYPE: ObjectType = ObjectType::{object_type};
+}}"
+        ))]
+    }
+
+    fn command_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let definition_name = &converter.command_definition(name).proto.name;
+        let pfn = RustBackend
+            .command_blocks(converter, definition_name)
+            .into_iter()
+            .next()
+            .map(|block| block.code)
+            .unwrap_or_default();
+        vec![
+            vulkanalia_block(pfn),
+            CodeBlock::new("rs", "Rust (vulkanalia trait)", command_signature(converter, name)),
+        ]
+    }
+
+    fn funcpointer_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        relabel(RustBackend.funcpointer_blocks(converter, name))
+    }
+
+    fn constant_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        relabel(RustBackend.constant_blocks(converter, name))
+    }
+
+    fn define_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        relabel(RustBackend.define_blocks(converter, name))
+    }
+
+    fn basetype_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        relabel(RustBackend.basetype_blocks(converter, name))
+    }
+}
+
+fn vulkanalia_block(code: String) -> CodeBlock {
+    CodeBlock::new("rs", "Rust (vulkanalia)", code)
+}
+
+fn relabel(blocks: Vec<CodeBlock>) -> Vec<CodeBlock> {
+    blocks.into_iter().map(|block| vulkanalia_block(block.code)).collect()
+}
+
+fn rs_name(name: &str) -> &str {
+    name.strip_prefix("Vk").unwrap_or(name)
+}
+
+/// vulkanalia drops the Hungarian `p`/`pp` prefix of fields and parameters, so `pNext` is `next`.
+fn field_name(name: &str) -> String {
+    let stripped = name
+        .strip_prefix("pp")
+        .or_else(|| name.strip_prefix('p'))
+        .filter(|rest| rest.starts_with(|c: char| c.is_ascii_uppercase()))
+        .unwrap_or(name);
+    match stripped.to_snake_case().as_str() {
+        "type" => "type_".to_string(),
+        name => name.to_string(),
+    }
+}
+
+/// The Vulkan members of a struct with their parsed declarations. Bitfields are left out as
+/// vulkanalia does not expose them as fields of their own.
+fn struct_members<'a>(converter: &'a Converter, name: &str) -> Vec<(&'a vk_parse::TypeMemberDefinition, c_decl::CDecl)> {
+    let vk_parse::TypeSpec::Members(members) = &converter.types[name].spec else {
+        unimplemented!()
+    };
+    members
+        .iter()
+        .filter_map(|member| match member {
+            vk_parse::TypeMember::Definition(def) if def.api.as_deref().map_or(true, |api| api == "vulkan") => {
+                let decl = c_decl::parse_declaration(&def.code).unwrap();
+                decl.bitfield.is_none().then_some((def, decl))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn is_struct(converter: &Converter, name: &str) -> bool {
+    converter
+        .types
+        .get(converter.resolve_type_alias(name))
+        .and_then(|ty| ty.category.as_deref())
+        .map_or(false, |category| category == "struct" || category == "union")
+}
+
+/// The type a builder setter or wrapper takes for a struct value, accepting builders too.
+fn cast_type(converter: &Converter, ty: &c_decl::CType) -> String {
+    if is_struct(converter, ty.base_name()) {
+        format!("impl Cast<Target = {}>", ty.to_rust())
+    } else {
+        ty.to_rust()
+    }
+}
+
+/// The `builder()` of a struct and the setters of its builder. Count members are set together
+/// with their array and `push_next` is offered when other structs extend this one.
+fn builder(converter: &Converter, name: &str) -> String {
+    let rs_name = rs_name(name);
+    let members = struct_members(converter, name);
+    let is_count = |member: &str| {
+        members
+            .iter()
+            .any(|(def, _)| def.len.as_deref().and_then(|len| len.split(',').next()) == Some(member))
+    };
+    let mut setters = String::new();
+    if !extending_structs(converter, name).is_empty() {
+        setters += &format!(
+            "    pub fn push_next<T>(mut self, next: &'b mut impl Cast<Target = T>) -> Self
+    where
+        T: Extends{rs_name};
+"
+        );
+    }
+    for (def, decl) in members.iter() {
+        let member = decl.name.as_deref().unwrap();
+        if member == "sType" || member == "pNext" || is_count(member) {
+            continue;
+        }
+        let field = field_name(member);
+        let ty = match &decl.ty {
+            c_decl::CType::Named { name, .. } if name == "VkBool32" => "bool".to_string(),
+            c_decl::CType::Pointer { pointee, .. } if !pointee.is_void() => {
+                let reference = if pointee.is_const() { "&'b" } else { "&'b mut" };
+                match def.len.as_deref() {
+                    Some("null-terminated") => format!("{reference} [u8]"),
+                    Some(_) => format!("{reference} [{}]", cast_type(converter, pointee)),
+                    None => format!("{reference} {}", cast_type(converter, pointee)),
+                }
+            }
+            ty => cast_type(converter, ty),
+        };
+        setters += &format!("    pub fn {field}(mut self, {field}: {ty}) -> Self;\n");
+    }
+    format!(
+        "impl {rs_name} {{
+    pub fn builder<'b>() -> {rs_name}Builder<'b>;
+}}
+
+impl<'b> {rs_name}Builder<'b> {{
+{setters}    pub fn build(self) -> {rs_name};
+}}"
+    )
+}
+
+/// The trait declaring a command: `DeviceV1_0` and friends for core versions, or the extension
+/// trait such as `KhrSwapchainExtension`.
+fn command_trait(converter: &Converter, name: &str) -> Option<String> {
+    let parents = converter.parents.get(name)?;
+    if let Some(version) = parents.split(", ").find_map(|parent| parent.strip_prefix("VK_VERSION_")) {
+        let level = match converter.dispatch_level(converter.command_definition(name)) {
+            DispatchLevel::Global => "Entry",
+            DispatchLevel::Instance => "Instance",
+            DispatchLevel::Device => "Device",
+        };
+        return Some(format!("{level}V{version}"));
+    }
+    let extension = parents
+        .split(", ")
+        .find(|parent| converter.extensions.contains_key(*parent))?;
+    Some(format!(
+        "{}Extension",
+        extension.strip_prefix("VK_").unwrap().to_upper_camel_case()
+    ))
+}
+
+/// The method of the command trait. Slices replace count and array pairs, the trailing output
+/// parameter is returned unless it is an extensible struct, which is filled through `&mut`, and
+/// `VkResult` becomes `VkResult<T>`, or `VkSuccessResult<T>` when the command has success codes
+/// besides `VK_SUCCESS`.
+fn command_signature(converter: &Converter, name: &str) -> String {
+    let command = converter.command_definition(name);
+    let params = command
+        .params
+        .iter()
+        .filter(|param| param.api.as_ref().map(String::as_str) != Some("vulkansc"))
+        .collect::<Vec<_>>();
+    let is_count = |name: &str| params.iter().any(|param| param.len.as_deref() == Some(name));
+
+    let mut rs_params = vec!["&self".to_string()];
+    let mut output = None;
+    let mut returns_vec = false;
+    for (i, param) in params.iter().enumerate() {
+        let decl = c_decl::parse_declaration(&param.definition.code).unwrap();
+        let param_name = param.definition.name.as_str();
+        // Only the handle the trait is implemented on is elided, other handles are still passed.
+        if i == 0 && matches!(decl.ty.base_name(), "VkDevice" | "VkInstance") {
+            continue;
+        }
+        if is_count(param_name) {
+            continue;
+        }
+        let rs_name = field_name(param_name);
+        let c_decl::CType::Pointer { pointee, .. } = &decl.ty else {
+            let ty = match decl.ty.base_name() {
+                "VkBool32" => "bool".to_string(),
+                _ => decl.ty.to_rust(),
+            };
+            rs_params.push(format!("{rs_name}: {ty}"));
+            continue;
+        };
+        let optional = param.optional.as_deref().map_or(false, |optional| optional.starts_with("true"));
+        let is_last = i == params.len() - 1;
+        let element = pointee.to_rust();
+        if pointee.is_void() {
+            let ty = match (param.len.is_some(), pointee.is_const()) {
+                (true, true) => "&[u8]",
+                (true, false) => "&mut [u8]",
+                (false, true) => "*const c_void",
+                (false, false) => "*mut c_void",
+            };
+            rs_params.push(format!("{rs_name}: {ty}"));
+        } else if pointee.is_const() {
+            let ty = match param.len.as_deref() {
+                Some("null-terminated") => "&[u8]".to_string(),
+                Some(_) => format!("&[{}]", cast_type(converter, pointee)),
+                None => format!("&{}", cast_type(converter, pointee)),
+            };
+            if optional && param.len.is_none() {
+                rs_params.push(format!("{rs_name}: Option<{ty}>"));
+            } else {
+                rs_params.push(format!("{rs_name}: {ty}"));
+            }
+        } else if is_last
+            && param.len.as_ref().map_or(false, |len| {
+                len.contains("->")
+                    || params
+                        .iter()
+                        .any(|count| count.definition.name == *len && count.definition.code.contains('*'))
+            })
+        {
+            output = Some(format!("Vec<{element}>"));
+            returns_vec = true;
+        } else if param.len.is_some() {
+            rs_params.push(format!("{rs_name}: &mut [{element}]"));
+        } else if is_last && !converter.has_s_type(converter.resolve_type_alias(pointee.base_name())) {
+            output = Some(element);
+        } else {
+            rs_params.push(format!("{rs_name}: &mut {element}"));
+        }
+    }
+
+    let return_type = match command.proto.type_name.as_deref().unwrap_or("void") {
+        "VkResult" => {
+            let has_extra_codes = command
+                .successcodes
+                .iter()
+                .flat_map(|codes| codes.split(','))
+                .any(|code| code != "VK_SUCCESS" && !(returns_vec && code == "VK_INCOMPLETE"));
+            match (has_extra_codes, output) {
+                (true, Some(output)) => format!(" -> VkSuccessResult<{output}>"),
+                (true, None) => " -> VkResult<SuccessCode>".to_string(),
+                (false, output) => format!(" -> VkResult<{}>", output.unwrap_or_else(|| "()".to_string())),
+            }
+        }
+        "void" => output.map(|output| format!(" -> {output}")).unwrap_or_default(),
+        "VkBool32" => " -> bool".to_string(),
+        return_type => format!(" -> {}", c_decl::parse_type(return_type).unwrap().to_rust()),
+    };
+    let method = name.strip_prefix("vk").unwrap().to_snake_case();
+    let rs_params = rs_params
+        .iter()
+        .fold(String::new(), |a, b| a + "    " + b + ",\n");
+    let header = command_trait(converter, name)
+        .map(|command_trait| format!("// vk::{command_trait}\n"))
+        .unwrap_or_default();
+    format!("{header}unsafe fn {method}(\n{rs_params}){return_type};")
+}
+
+/// Flags as vulkanalia declares them, through the `bitflags!` macro.
+fn bitflags(converter: &Converter, name: &str) -> String {
+    let backing = if converter.flags_bitwidth(name) == 64 { "Flags64" } else { "Flags" };
+    let bits = converter
+        .flag_bits_type(name)
+        .map(|bits_name| {
+            converter
+                .enum_values(bits_name)
+                .iter()
+                .map(|bit| {
+                    let constant = variant_ident(bits_name, &bit.name);
+                    let value = match (&bit.alias, bit.bitpos) {
+                        (Some(alias), _) => format!("Self::{}.bits()", variant_ident(bits_name, alias)),
+                        (None, Some(bitpos)) => format!("1 << {bitpos}"),
+                        (None, None) => bit.value.to_string(),
+                    };
+                    format!("        const {constant} = {value};\n")
+                })
+                .collect::<String>()
+        })
+        .unwrap_or_default();
+    format!(
+        "bitflags! {{
+    #[repr(transparent)]
+    #[derive(Default)]
+    pub struct {}: {backing} {{
+{bits}    }}
+}}",
+        rs_name(name)
+    )
+}
@@ -0,0 +1,200 @@
+use std::{collections::HashMap, ops::Range};
+
+use super::{CodeBlock, LanguageBackend};
+use crate::Converter;
+
+/// The registry entries themselves, cut out of vk.xml. Opt-in through `VK_DOC_TABS` as the
+/// fragments can be long.
+pub(crate) struct XmlBackend {
+    xml: String,
+    /// Byte ranges of the top level registry elements, keyed by element and `name`.
+    elements: HashMap<(&'static str, String), Vec<Range<usize>>>,
+}
+
+/// The elements that can be shown, with the parent they are looked up in.
+const INDEXED_ELEMENTS: &[(&str, &str)] = &[
+    ("types", "type"),
+    ("commands", "command"),
+    ("registry", "enums"),
+    ("enums", "enum"),
+    ("extensions", "extension"),
+];
+
+impl XmlBackend {
+    pub fn load() -> Self {
+        let xml = std::fs::read_to_string("./Vulkan-Docs/xml/vk.xml").unwrap();
+        let elements = index_elements(&xml);
+        Self { xml, elements }
+    }
+
+    fn blocks(&self, element: &'static str, name: &str) -> Vec<CodeBlock> {
+        let Some(ranges) = self.elements.get(&(element, name.to_string())) else {
+            return Vec::new();
+        };
+        ranges
+            .iter()
+            .map(|range| {
+                let fragment = self.fragment(range.clone());
+                let opening = &fragment[..tag_end(&fragment)];
+                let label = if attribute(opening, "api") == Some("vulkansc") {
+                    "XML (Vulkan SC)"
+                } else {
+                    "XML"
+                };
+                CodeBlock::new("xml", label, fragment)
+            })
+            .collect()
+    }
+
+    /// The element with the indentation of its first line removed from every line, and parts
+    /// that only apply to Vulkan SC marked with a comment.
+    fn fragment(&self, range: Range<usize>) -> String {
+        let line_start = self.xml[..range.start].rfind('\n').map_or(0, |i| i + 1);
+        let indent = &self.xml[line_start..range.start];
+        let indent = if indent.trim().is_empty() { indent } else { "" };
+        self.xml[range]
+            .lines()
+            .map(|line| {
+                let line = line.strip_prefix(indent).unwrap_or(line);
+                if line.contains("api=\"vulkansc\"") && line.trim_end().ends_with('>') {
+                    format!("{line} <!-- Vulkan SC only -->")
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl LanguageBackend for XmlBackend {
+    fn struct_blocks(&self, _converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        self.blocks("type", name)
+    }
+
+    fn enum_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        if !converter.enums.contains_key(name) {
+            return self.blocks("type", name);
+        }
+        self.blocks("enums", name)
+    }
+
+    fn flags_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let mut blocks = self.blocks("type", name);
+        if converter.types[name].alias.is_none() {
+            if let Some(bits_name) = converter.flag_bits_type(name) {
+                blocks.extend(self.blocks("enums", bits_name));
+            }
+        }
+        blocks
+    }
+
+    fn handle_blocks(&self, _converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        self.blocks("type", name)
+    }
+
+    fn command_blocks(&self, _converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        self.blocks("command", name)
+    }
+
+    fn funcpointer_blocks(&self, _converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        self.blocks("type", name)
+    }
+
+    fn constant_blocks(&self, _converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        self.blocks("enum", name)
+    }
+
+    fn define_blocks(&self, _converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        self.blocks("type", name)
+    }
+
+    fn basetype_blocks(&self, _converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        self.blocks("type", name)
+    }
+
+    fn extension_blocks(&self, _converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        self.blocks("extension", name)
+    }
+}
+
+/// Walks the tags of vk.xml and records where each element of [`INDEXED_ELEMENTS`] starts and
+/// ends. Elements are named by their `name` attribute, or else by their first `<name>` child as
+/// in `<type category="handle">` and `<command><proto>`.
+fn index_elements(xml: &str) -> HashMap<(&'static str, String), Vec<Range<usize>>> {
+    let mut elements: HashMap<_, Vec<_>> = HashMap::new();
+    // Open elements with their start offset.
+    let mut stack: Vec<(&str, usize)> = Vec::new();
+    let mut pos = 0;
+    while let Some(offset) = xml[pos..].find('<') {
+        let start = pos + offset;
+        let rest = &xml[start..];
+        if rest.starts_with("<!--") {
+            pos = start + rest.find("-->").unwrap() + 3;
+            continue;
+        }
+        if rest.starts_with("<?") || rest.starts_with("<!") {
+            pos = start + rest.find('>').unwrap() + 1;
+            continue;
+        }
+        let end = start + tag_end(rest) + 1;
+        let tag = &xml[start..end];
+        pos = end;
+
+        let (closed, element_start) = if let Some(name) = tag.strip_prefix("</") {
+            let name = name.trim_end_matches('>').trim();
+            let (open, element_start) = stack.pop().unwrap();
+            assert_eq!(open, name);
+            (name, element_start)
+        } else {
+            let name = tag[1..]
+                .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+                .next()
+                .unwrap();
+            if !tag.ends_with("/>") {
+                stack.push((name, start));
+                continue;
+            }
+            (name, start)
+        };
+
+        let parent = stack.last().map_or("", |(parent, _)| *parent);
+        let Some((_, element)) = INDEXED_ELEMENTS
+            .iter()
+            .find(|(indexed_parent, element)| *element == closed && *indexed_parent == parent)
+        else {
+            continue;
+        };
+        let fragment = &xml[element_start..end];
+        let opening = &fragment[..tag_end(fragment)];
+        let name = attribute(opening, "name").map(str::to_string).or_else(|| {
+            let name_start = fragment.find("<name>")? + "<name>".len();
+            let name_end = name_start + fragment[name_start..].find("</name>")?;
+            Some(fragment[name_start..name_end].to_string())
+        });
+        if let Some(name) = name {
+            elements.entry((*element, name)).or_default().push(element_start..end);
+        }
+    }
+    elements
+}
+
+/// The offset of the `>` closing the tag at the start of `tag`, skipping quoted attributes.
+fn tag_end(tag: &str) -> usize {
+    let mut quoted = false;
+    for (i, c) in tag.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '>' if !quoted => return i,
+            _ => (),
+        }
+    }
+    unreachable!("unterminated tag")
+}
+
+fn attribute<'a>(opening_tag: &'a str, name: &str) -> Option<&'a str> {
+    let pattern = format!(" {name}=\"");
+    let start = opening_tag.find(&pattern)? + pattern.len();
+    let end = start + opening_tag[start..].find('"')?;
+    Some(&opening_tag[start..end])
+}
@@ -0,0 +1,505 @@
+use heck::{ToShoutySnakeCase, ToSnakeCase};
+use regex::Regex;
+
+use super::{lower_first, tagged_camel_case, vendor_tag, CodeBlock, LanguageBackend};
+use crate::{c_decl, constants, Converter, DispatchLevel};
+
+/// The bindings generated by vulkan-zig, with its snake_case fields and packed struct flags.
+pub(crate) struct ZigBackend;
+
+impl LanguageBackend for ZigBackend {
+    fn struct_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let ty = &converter.types[name];
+        if let Some(alias) = &ty.alias {
+            return vec![zig_block(format!("pub const {} = {};", zig_name(name), zig_name(alias)))];
+        }
+        let is_union = ty.category.as_deref() == Some("union");
+        let vk_parse::TypeSpec::Members(members) = &ty.spec else {
+            unimplemented!()
+        };
+        let definitions = members
+            .iter()
+            .filter_map(|member| match member {
+                vk_parse::TypeMember::Definition(def) if def.api.as_deref().map_or(true, |api| api == "vulkan") => {
+                    Some(def)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let groups = converter.bitfield_groups(name);
+        let mut fields = String::new();
+        for def in definitions.iter() {
+            let decl = c_decl::parse_declaration(&def.code).unwrap();
+            let member_name = decl.name.as_deref().unwrap();
+            if decl.bitfield.is_some() {
+                // Bitfields sharing a storage unit become one packed struct field.
+                let Some(group) = groups.iter().find(|group| group.members[0].0 == member_name) else {
+                    continue;
+                };
+                let storage = zig_named_type(&group.storage);
+                let packed = group
+                    .members
+                    .iter()
+                    .map(|(member, width)| format!("{}: u{width}", zig_field_name(member)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                fields += &format!("    {}: packed struct({storage}) {{ {packed} }},\n", group.rust_field());
+                continue;
+            }
+            // Arrays are optional when their length member is.
+            let optional = def.optional.as_deref().or_else(|| {
+                let len = def.len.as_deref()?.split(',').next()?;
+                let len_member = definitions.iter().find(|member| {
+                    member
+                        .markup
+                        .iter()
+                        .any(|markup| matches!(markup, vk_parse::TypeMemberMarkup::Name(name) if name == len))
+                })?;
+                len_member.optional.as_deref()
+            });
+            let zig_type = zig_decl_type(&decl.ty, optional, def.len.as_deref());
+            let default = if is_union {
+                String::new()
+            } else if let (Some(values), "sType") = (&def.values, member_name) {
+                format!(" = .{}", zig_enum_field(converter, "VkStructureType", values))
+            } else if zig_type.starts_with('?') {
+                " = null".to_string()
+            } else if converter.types.get(decl.ty.base_name()).and_then(|ty| ty.category.as_deref()) == Some("bitmask")
+                && matches!(decl.ty, c_decl::CType::Named { .. })
+            {
+                " = .{}".to_string()
+            } else {
+                String::new()
+            };
+            fields += &format!("    {}: {zig_type}{default},\n", zig_field_name(member_name));
+        }
+        let keyword = if is_union { "extern union" } else { "extern struct" };
+        vec![zig_block(format!("pub const {} = {keyword} {{\n{fields}}};", zig_name(name)))]
+    }
+
+    fn enum_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let Some(e) = converter.enums.get(name) else {
+            let alias = converter.types[name].alias.as_ref().unwrap();
+            return vec![zig_block(format!("pub const {} = {};", zig_name(name), zig_name(alias)))];
+        };
+        if e.kind.as_deref() == Some("bitmask") {
+            // vulkan-zig has no separate FlagBits type, the bits are fields of the flags struct.
+            let Some(flags) = converter.flags_type_of(name) else {
+                return Vec::new();
+            };
+            return vec![zig_block(packed_flags(converter, flags))];
+        }
+        let mut fields = String::new();
+        let mut aliases = String::new();
+        for value in converter.enum_values(name) {
+            let field = zig_enum_field(converter, name, &value.name);
+            match &value.alias {
+                None => fields += &format!("    {field} = {},\n", value.value),
+                Some(alias) => {
+                    aliases += &format!(
+                        "    pub const {field} = {}.{};\n",
+                        zig_name(name),
+                        zig_enum_field(converter, name, alias)
+                    )
+                }
+            }
+        }
+        vec![zig_block(format!(
+            "pub const {} = enum(i32) {{\n{fields}    _,\n{aliases}}};",
+            zig_name(name)
+        ))]
+    }
+
+    fn flags_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        if let Some(alias) = &converter.types[name].alias {
+            return vec![zig_block(format!("pub const {} = {};", zig_name(name), zig_name(alias)))];
+        }
+        vec![zig_block(packed_flags(converter, name))]
+    }
+
+    fn handle_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        if let Some(alias) = &converter.types[name].alias {
+            return vec![zig_block(format!("pub const {} = {};", zig_name(name), zig_name(alias)))];
+        }
+        let backing = if converter.is_dispatchable_handle(name) { "usize" } else { "u64" };
+        vec![zig_block(format!(
+            "pub const {} = enum({backing}) {{ null_handle = 0, _ }};",
+            zig_name(name)
+        ))]
+    }
+
+    fn command_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        vec![
+            zig_block(command_pfn(converter, name)),
+            CodeBlock::new("zig", "Zig (wrapper)", command_wrapper(converter, name)),
+        ]
+    }
+
+    fn funcpointer_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let vk_parse::TypeSpec::Code(code) = &converter.types[name].spec else {
+            unimplemented!()
+        };
+        let decl = c_decl::parse_declaration(&code.code).unwrap();
+        let c_decl::CType::Pointer { pointee, .. } = &decl.ty else {
+            unimplemented!()
+        };
+        let c_decl::CType::Function { return_type, params } = pointee.as_ref() else {
+            unimplemented!()
+        };
+        // Without registry attributes every pointer parameter of a callback may be null.
+        let params = params
+            .iter()
+            .map(|param| {
+                format!(
+                    "{}: {}",
+                    zig_field_name(param.name.as_deref().unwrap_or("_")),
+                    zig_decl_type(&param.ty, Some("true"), None)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        vec![zig_block(format!(
+            "pub const {} = ?*const fn ({params}) callconv(vulkan_call_conv) {};",
+            zig_name(name),
+            zig_decl_type(return_type, Some("true"), None)
+        ))]
+    }
+
+    fn constant_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let zig_constant = name.strip_prefix("VK_").unwrap();
+        let value = match &converter.consts[name].spec {
+            vk_parse::EnumSpec::Value { value, .. } => {
+                let (target, _) = converter.evaluate_constant(name);
+                let zig_type = zig_named_type(target.type_suffix.as_deref().unwrap());
+                let literal = constants::rust_literal(value).unwrap();
+                match literal.strip_prefix('!') {
+                    Some(complement) => format!("~@as({zig_type}, {complement})"),
+                    None => format!("@as({zig_type}, {literal})"),
+                }
+            }
+            vk_parse::EnumSpec::Alias { alias, .. } => alias.strip_prefix("VK_").unwrap().to_string(),
+            _ => unimplemented!(),
+        };
+        vec![zig_block(format!("pub const {zig_constant} = {value};"))]
+    }
+
+    fn define_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let zig_define = name.strip_prefix("VK_").unwrap_or(name);
+        let code = match name {
+            "VK_MAKE_API_VERSION" => {
+                "pub fn makeApiVersion(variant: u3, major: u7, minor: u10, patch: u12) Version".to_string()
+            }
+            "VK_API_VERSION_VARIANT" | "VK_API_VERSION_MAJOR" | "VK_API_VERSION_MINOR" | "VK_API_VERSION_PATCH" => {
+                "pub const Version = packed struct(u32) {
+    patch: u12,
+    minor: u10,
+    major: u7,
+    variant: u3,
+};"
+                .to_string()
+            }
+            _ => {
+                if let Some(version) = converter.define_api_version(name) {
+                    format!(
+                        "pub const {zig_define} = makeApiVersion({}, {}, {}, {});",
+                        version.variant, version.major, version.minor, version.patch
+                    )
+                } else if let Some(number) = converter.define_number(name) {
+                    format!("pub const {zig_define} = {number};")
+                } else {
+                    return Vec::new();
+                }
+            }
+        };
+        vec![zig_block(code)]
+    }
+
+    fn basetype_blocks(&self, converter: &Converter, name: &str) -> Vec<CodeBlock> {
+        let zig_name = zig_name(name);
+        if name == "VkBool32" {
+            return vec![zig_block(format!("pub const {zig_name} = enum(i32) {{ false, true, _ }};"))];
+        }
+        let vk_parse::TypeSpec::Code(code) = &converter.types[name].spec else {
+            unimplemented!()
+        };
+        // Opaque platform structs such as `struct ANativeWindow;` have no typedef. The Metal
+        // `*_id` types start with an Objective-C `id<MTLDevice>` typedef under `#ifdef __OBJC__`,
+        // which is skipped in favour of the plain C one of the `#else` branch.
+        let typedef = code
+            .code
+            .lines()
+            .filter(|line| line.trim_start().starts_with("typedef"))
+            .find_map(|typedef| c_decl::parse_declaration(typedef).ok());
+        let code = match typedef {
+            Some(decl) => format!("pub const {zig_name} = {};", zig_decl_type(&decl.ty, None, None)),
+            None => format!("pub const {zig_name} = opaque {{}};"),
+        };
+        vec![zig_block(code)]
+    }
+}
+
+fn zig_block(code: String) -> CodeBlock {
+    CodeBlock::new("zig", "Zig", code)
+}
+
+/// vulkan-zig drops the `Vk` prefix and renames `PFN_vkFoo` to `PfnFoo`.
+fn zig_name(name: &str) -> String {
+    if let Some(name) = name.strip_prefix("PFN_vk") {
+        return format!("Pfn{name}");
+    }
+    name.strip_prefix("Vk").unwrap_or(name).to_string()
+}
+
+fn zig_field_name(name: &str) -> String {
+    let name = name.to_snake_case();
+    match name.as_str() {
+        "type" | "error" | "align" | "enum" | "union" | "struct" | "opaque" => format!("@\"{name}\""),
+        _ => name,
+    }
+}
+
+fn zig_named_type(name: &str) -> String {
+    match name {
+        "char" | "uint8_t" => "u8",
+        "int8_t" => "i8",
+        "uint16_t" => "u16",
+        "int16_t" => "i16",
+        "uint32_t" => "u32",
+        "int32_t" => "i32",
+        "uint64_t" => "u64",
+        "int64_t" => "i64",
+        "size_t" => "usize",
+        "int" => "c_int",
+        "float" => "f32",
+        "double" => "f64",
+        name => return zig_name(name),
+    }
+    .to_string()
+}
+
+/// The Zig type of a member or parameter. `optional` and `len` are the comma separated
+/// attributes of vk.xml, with one entry per level of indirection.
+fn zig_decl_type(ty: &c_decl::CType, optional: Option<&str>, len: Option<&str>) -> String {
+    let optional = optional.map(|optional| optional.split(',').collect::<Vec<_>>()).unwrap_or_default();
+    let len = len.map(|len| len.split(',').collect::<Vec<_>>()).unwrap_or_default();
+    zig_type_level(ty, &optional, &len, 0)
+}
+
+fn zig_type_level(ty: &c_decl::CType, optional: &[&str], len: &[&str], level: usize) -> String {
+    match ty {
+        c_decl::CType::Named { name, .. } => zig_named_type(name),
+        c_decl::CType::Pointer { pointee, .. } => {
+            if let c_decl::CType::Function { .. } = pointee.as_ref() {
+                unimplemented!()
+            }
+            let optional = if optional.get(level) == Some(&"true") { "?" } else { "" };
+            let constness = if pointee.is_const() { "const " } else { "" };
+            if pointee.is_void() {
+                return format!("{optional}*{constness}anyopaque");
+            }
+            let pointer = match len.get(level) {
+                Some(&"null-terminated") => "[*:0]",
+                Some(_) => "[*]",
+                None => "*",
+            };
+            format!(
+                "{optional}{pointer}{constness}{}",
+                zig_type_level(pointee, optional, len, level + 1)
+            )
+        }
+        c_decl::CType::Array { element, len: array_len } => {
+            let array_len = match array_len {
+                c_decl::ArrayLen::Literal(len) => len.to_string(),
+                c_decl::ArrayLen::Constant(name) => name.strip_prefix("VK_").unwrap_or(name).to_string(),
+            };
+            format!("[{array_len}]{}", zig_type_level(element, optional, len, level))
+        }
+        c_decl::CType::Function { .. } => unimplemented!(),
+    }
+}
+
+/// The enum prefix and, when it matches the enum's own tag, the vendor suffix are dropped and
+/// the rest is lower cased, e.g. `VK_FORMAT_R8G8B8A8_UNORM` becomes `r8g8b8a8_unorm`.
+fn zig_enum_field(converter: &Converter, enum_name: &str, value: &str) -> String {
+    let prefix = enum_name.replace("FlagBits", "").to_shouty_snake_case();
+    let tag = vendor_tag(converter, &prefix);
+    let prefix = match tag {
+        Some(tag) => prefix.strip_suffix(&format!("_{tag}")).unwrap().to_string(),
+        None => prefix,
+    };
+    let prefix = Regex::new("(\\d+)$").unwrap().replace(&prefix, "_$1");
+    let value = match tag {
+        Some(tag) => value.strip_suffix(&format!("_{tag}")).unwrap_or(value),
+        None => value,
+    };
+    let field = if enum_name == "VkResult" {
+        value.strip_prefix("VK_").unwrap()
+    } else {
+        value.strip_prefix(&*prefix).unwrap().strip_prefix('_').unwrap()
+    };
+    let field = field.to_lowercase();
+    if field.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("@\"{field}\"")
+    } else {
+        field
+    }
+}
+
+/// Flags as a `packed struct` of one bool per bit, with reserved fields for unused bits.
+fn packed_flags(converter: &Converter, name: &str) -> String {
+    let width = converter.flags_bitwidth(name);
+    let backing = if width == 64 { "Flags64" } else { "Flags" };
+    let bits = converter
+        .flag_bits_type(name)
+        .map(|bits_name| {
+            converter
+                .enum_values(bits_name)
+                .into_iter()
+                .filter(|bit| bit.alias.is_none())
+                .filter_map(|bit| Some((bit.bitpos?, zig_enum_field(converter, bits_name, &bit.name))))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let zig_name = zig_name(name);
+    if bits.is_empty() {
+        return format!(
+            "pub const {zig_name} = packed struct({backing}) {{
+    _reserved_bits: {backing} = 0,
+    pub usingnamespace FlagsMixin({zig_name});
+}};"
+        );
+    }
+    let fields = (0..width)
+        .map(|bitpos| match bits.iter().find(|(position, _)| *position == bitpos) {
+            Some((_, field)) => format!("    {field}: bool = false,\n"),
+            None => format!("    _reserved_bit_{bitpos}: bool = false,\n"),
+        })
+        .collect::<String>();
+    format!(
+        "pub const {zig_name} = packed struct({backing}) {{
+{fields}    pub usingnamespace FlagsMixin({zig_name});
+}};"
+    )
+}
+
+fn command_pfn(converter: &Converter, name: &str) -> String {
+    let command = converter.command_definition(name);
+    let params = command
+        .params
+        .iter()
+        .filter(|param| param.api.as_ref().map(String::as_str) != Some("vulkansc"))
+        .map(|param| {
+            let decl = c_decl::parse_declaration(&param.definition.code).unwrap();
+            format!(
+                "    {}: {},\n",
+                zig_field_name(&param.definition.name),
+                zig_decl_type(&decl.ty, param.optional.as_deref(), param.len.as_deref())
+            )
+        })
+        .collect::<String>();
+    let return_type = zig_named_type(command.proto.type_name.as_deref().unwrap_or("void"));
+    format!(
+        "pub const {} = *const fn (\n{params}) callconv(vulkan_call_conv) {return_type};",
+        zig_name(&format!("PFN_{name}"))
+    )
+}
+
+/// The method of vulkan-zig's dispatch wrappers: const structs are passed by value, a trailing
+/// output parameter becomes the return value and `VkResult` becomes an error union.
+fn command_wrapper(converter: &Converter, name: &str) -> String {
+    let command = converter.command_definition(name);
+    let params = command
+        .params
+        .iter()
+        .filter(|param| param.api.as_ref().map(String::as_str) != Some("vulkansc"))
+        .collect::<Vec<_>>();
+    let is_count = |name: &str| {
+        params
+            .iter()
+            .any(|param| param.len.as_deref().and_then(|len| len.split(',').next()) == Some(name))
+    };
+
+    let mut zig_params = vec!["self: Self".to_string()];
+    let mut output = None;
+    for (i, param) in params.iter().enumerate() {
+        let decl = c_decl::parse_declaration(&param.definition.code).unwrap();
+        let param_name = param.definition.name.as_str();
+        let zig_type = zig_decl_type(&decl.ty, param.optional.as_deref(), param.len.as_deref());
+        let c_decl::CType::Pointer { pointee, .. } = &decl.ty else {
+            zig_params.push(format!("{}: {zig_type}", zig_field_name(param_name)));
+            continue;
+        };
+        let is_struct = converter
+            .types
+            .get(pointee.base_name())
+            .and_then(|ty| ty.category.as_deref())
+            .map_or(false, |category| category == "struct" || category == "union");
+        let optional = param.optional.as_deref().map_or(false, |optional| optional.starts_with("true"));
+        let by_value_name = param_name
+            .strip_prefix('p')
+            .filter(|rest| rest.starts_with(char::is_uppercase))
+            .map_or_else(|| zig_field_name(param_name), zig_field_name);
+        if pointee.is_const() && is_struct && param.len.is_none() && !optional {
+            zig_params.push(format!("{by_value_name}: {}", zig_named_type(pointee.base_name())));
+        } else if i == params.len() - 1
+            && !pointee.is_const()
+            && param.len.is_none()
+            && !pointee.is_void()
+            && !is_count(param_name)
+        {
+            output = Some((
+                by_value_name,
+                zig_type.trim_start_matches('?').trim_start_matches('*').to_string(),
+            ));
+        } else {
+            zig_params.push(format!("{}: {zig_type}", zig_field_name(param_name)));
+        }
+    }
+
+    let method = lower_first(name.strip_prefix("vk").unwrap());
+    let type_name = name.strip_prefix("vk").unwrap();
+    let mut declarations = String::new();
+    let return_type = match command.proto.type_name.as_deref().unwrap_or("void") {
+        "VkResult" => {
+            let errors = command
+                .errorcodes
+                .iter()
+                .flat_map(|codes| codes.split(','))
+                .filter(|code| *code != "VK_ERROR_UNKNOWN")
+                .map(|code| {
+                    let error = code.strip_prefix("VK_ERROR_").unwrap_or(code);
+                    format!("    {},\n", tagged_camel_case(converter, error))
+                })
+                .collect::<String>();
+            declarations += &format!("pub const {type_name}Error = error{{\n{errors}    Unknown,\n}};\n");
+            let has_extra_codes = command
+                .successcodes
+                .iter()
+                .flat_map(|codes| codes.split(','))
+                .any(|code| code != "VK_SUCCESS");
+            let value = match (has_extra_codes, output) {
+                (true, Some((field, ty))) => {
+                    declarations += &format!(
+                        "pub const {type_name}Result = struct {{\n    result: Result,\n    {field}: {ty},\n}};\n"
+                    );
+                    format!("{type_name}Result")
+                }
+                (true, None) => "Result".to_string(),
+                (false, Some((_, ty))) => ty,
+                (false, None) => "void".to_string(),
+            };
+            format!("{type_name}Error!{value}")
+        }
+        "void" => output.map(|(_, ty)| ty).unwrap_or_else(|| "void".to_string()),
+        return_type => zig_named_type(return_type),
+    };
+    let wrapper = match converter.dispatch_level(command) {
+        DispatchLevel::Global => "BaseWrapper",
+        DispatchLevel::Instance => "InstanceWrapper",
+        DispatchLevel::Device => "DeviceWrapper",
+    };
+    let zig_params = zig_params
+        .iter()
+        .fold(String::new(), |a, b| a + "    " + b + ",\n");
+    format!("// vk.{wrapper}\n{declarations}pub fn {method}(\n{zig_params}) {return_type}")
+}
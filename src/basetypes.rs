@@ -0,0 +1,116 @@
+use std::{collections::BTreeMap, fs::File, io::Write};
+
+use crate::{c_decl, json_string, Converter};
+
+/// Rust representations of the platform types ash declares in `vk::platform_types`.
+const PLATFORM_TYPES: &[(&str, &str)] = &[
+    ("RROutput", "std::ffi::c_ulong"),
+    ("VisualID", "std::ffi::c_uint"),
+    ("Display", "std::ffi::c_void"),
+    ("Window", "std::ffi::c_ulong"),
+    ("xcb_connection_t", "std::ffi::c_void"),
+    ("xcb_window_t", "u32"),
+    ("xcb_visualid_t", "u32"),
+    ("HANDLE", "isize"),
+    ("HINSTANCE", "vk::HANDLE"),
+    ("HWND", "vk::HANDLE"),
+    ("HMONITOR", "vk::HANDLE"),
+    ("wl_display", "std::ffi::c_void"),
+    ("wl_surface", "std::ffi::c_void"),
+    ("DWORD", "std::ffi::c_ulong"),
+    ("LPCWSTR", "*const u16"),
+    ("zx_handle_t", "u32"),
+    ("_screen_buffer", "std::ffi::c_void"),
+    ("_screen_context", "std::ffi::c_void"),
+    ("_screen_window", "std::ffi::c_void"),
+    ("SECURITY_ATTRIBUTES", "std::ffi::c_void"),
+    ("ANativeWindow", "std::ffi::c_void"),
+    ("AHardwareBuffer", "std::ffi::c_void"),
+    ("CAMetalLayer", "std::ffi::c_void"),
+    ("GgpStreamDescriptor", "u32"),
+    ("GgpFrameToken", "u64"),
+    ("IDirectFB", "std::ffi::c_void"),
+    ("IDirectFBSurface", "std::ffi::c_void"),
+    ("__IOSurface", "std::ffi::c_void"),
+    ("IOSurfaceRef", "*mut vk::__IOSurface"),
+    ("MTLBuffer_id", "*mut std::ffi::c_void"),
+    ("MTLCommandQueue_id", "*mut std::ffi::c_void"),
+    ("MTLDevice_id", "*mut std::ffi::c_void"),
+    ("MTLSharedEvent_id", "*mut std::ffi::c_void"),
+    ("MTLTexture_id", "*mut std::ffi::c_void"),
+];
+
+impl Converter {
+    pub(crate) fn generate_basetype(&self, name: &str) -> String {
+        self.render_tabs(|backend| backend.basetype_blocks(self, name))
+    }
+
+    pub(crate) fn basetype_attributes(&self, name: &str) -> String {
+        format!("header: {}\n", self.type_header(name))
+    }
+
+    /// The Rust type ash uses for a `basetype` or an external type `require`d from a platform header.
+    pub(crate) fn basetype_rust(&self, name: &str) -> String {
+        if let Some((_, rs_type)) = PLATFORM_TYPES.iter().find(|(platform_type, _)| *platform_type == name) {
+            return rs_type.to_string();
+        }
+        if name.starts_with("StdVideo") {
+            return format!("vk::native::{name}");
+        }
+        let Some(vk_parse::TypeSpec::Code(code)) = self.types.get(name).map(|ty| &ty.spec) else {
+            return "std::ffi::c_void".to_string();
+        };
+        // Opaque platform structs such as `struct ANativeWindow;` have no typedef.
+        let Some(typedef) = code.code.lines().find(|line| line.trim_start().starts_with("typedef")) else {
+            return "std::ffi::c_void".to_string();
+        };
+        c_decl::parse_declaration(typedef).unwrap().ty.to_rust()
+    }
+
+    /// The header declaring a type: the platform header named by `requires` for external types,
+    /// otherwise the Vulkan header of the platform extension that requires it.
+    pub(crate) fn type_header(&self, name: &str) -> String {
+        if let Some(requires) = self.types.get(name).and_then(|ty| ty.requires.as_ref()) {
+            if requires.ends_with(".h") {
+                return requires.clone();
+            }
+        }
+        self.parents
+            .get(name)
+            .into_iter()
+            .flat_map(|parents| parents.split(", "))
+            .filter_map(|parent| self.extensions.get(parent)?.platform.as_ref())
+            .map(|platform| format!("vulkan_{platform}.h"))
+            .next()
+            .unwrap_or_else(|| "vulkan_core.h".to_string())
+    }
+
+    /// Writes the Rust mapping and header of every base type and external platform type to
+    /// `./dist/platform_types.json`.
+    pub(crate) fn write_platform_types(&self) {
+        let mut entries = BTreeMap::new();
+        for (name, ty) in self.types.iter() {
+            let is_basetype = ty.category.as_ref().map(String::as_str) == Some("basetype");
+            let is_external = ty.category.is_none()
+                && ty.requires.as_ref().map_or(false, |requires| requires.ends_with(".h"));
+            if !is_basetype && !is_external {
+                continue;
+            }
+            entries.insert(
+                name.as_str(),
+                format!(
+                    "{{ \"rust\": {}, \"header\": {} }}",
+                    json_string(&self.basetype_rust(name)),
+                    json_string(&self.type_header(name))
+                ),
+            );
+        }
+        let entries = entries
+            .iter()
+            .map(|(name, entry)| format!("\"{name}\": {entry}"))
+            .collect::<Vec<_>>()
+            .join(",\n    ");
+        let mut file = File::create("./dist/platform_types.json").unwrap();
+        file.write_fmt(format_args!("{{\n    {entries}\n}}")).unwrap();
+    }
+}
@@ -0,0 +1,107 @@
+use heck::ToSnakeCase;
+
+use crate::{c_decl, Converter};
+
+/// Consecutive C bitfield members sharing one storage unit, which ash exposes as a single
+/// `Packed24_8`-style field named after the members it combines.
+pub(crate) struct BitfieldGroup {
+    /// Member names and their widths in bits, from the least significant bit up.
+    pub members: Vec<(String, u32)>,
+    /// The declared type of the first member, which determines the size of the storage unit.
+    pub storage: String,
+}
+
+impl BitfieldGroup {
+    pub fn rust_field(&self) -> String {
+        self.members
+            .iter()
+            .map(|(name, _)| name.to_snake_case())
+            .collect::<Vec<_>>()
+            .join("_and_")
+    }
+
+    pub fn rust_type(&self) -> String {
+        let widths = self
+            .members
+            .iter()
+            .map(|(_, width)| width.to_string())
+            .collect::<Vec<_>>()
+            .join("_");
+        format!("Packed{widths}")
+    }
+}
+
+impl Converter {
+    /// The bitfield members of a struct, grouped into the storage units the C compiler packs them in.
+    pub(crate) fn bitfield_groups(&self, name: &str) -> Vec<BitfieldGroup> {
+        let Some(vk_parse::TypeSpec::Members(members)) = self.types.get(name).map(|ty| &ty.spec) else {
+            return Vec::new();
+        };
+        let mut groups: Vec<BitfieldGroup> = Vec::new();
+        let mut used_bits = 0;
+        for member in members.iter() {
+            let vk_parse::TypeMember::Definition(def) = member else {
+                continue;
+            };
+            if def.api.as_deref().map_or(false, |api| api != "vulkan") {
+                continue;
+            }
+            let decl = c_decl::parse_declaration(&def.code).unwrap();
+            let Some(width) = decl.bitfield else {
+                used_bits = 0;
+                continue;
+            };
+            let storage = decl.ty.base_name().to_string();
+            let storage_bits = bitfield_storage_bits(&storage);
+            let continues_group = used_bits != 0
+                && used_bits + width <= storage_bits
+                && groups
+                    .last()
+                    .map_or(false, |group| bitfield_storage_bits(&group.storage) == storage_bits);
+            if !continues_group {
+                groups.push(BitfieldGroup {
+                    members: Vec::new(),
+                    storage,
+                });
+                used_bits = 0;
+            }
+            groups.last_mut().unwrap().members.push((decl.name.unwrap(), width));
+            used_bits += width;
+        }
+        groups
+    }
+
+    pub(crate) fn bitfield_attributes(&self, name: &str) -> String {
+        let groups = self.bitfield_groups(name);
+        if groups.is_empty() {
+            return String::new();
+        }
+        let bitfields = groups
+            .iter()
+            .flat_map(|group| {
+                let rust_field = group.rust_field();
+                let rust_type = group.rust_type();
+                let mut offset = 0;
+                group.members.iter().map(move |(member, width)| {
+                    let entry = format!(
+                        "{{ member: {member}, bits: {width}, bit_offset: {offset}, storage: {}, rust_field: {rust_field}, rust_type: {rust_type} }}",
+                        group.storage
+                    );
+                    offset += width;
+                    entry
+                })
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("bitfields: [{bitfields}]\n")
+    }
+}
+
+fn bitfield_storage_bits(c_type: &str) -> u32 {
+    match c_type {
+        "uint8_t" | "int8_t" => 8,
+        "uint16_t" | "int16_t" => 16,
+        "uint64_t" | "int64_t" => 64,
+        _ => 32,
+    }
+}
@@ -0,0 +1,489 @@
+//! Parser for the C declarations found in vk.xml: struct members, command parameters and
+//! `funcpointer` typedefs. Declarators are turned into a [`CType`] tree which can then be
+//! mapped onto the equivalent Rust type.
+
+use heck::ToSnakeCase;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CType {
+    Named { name: String, is_const: bool },
+    Pointer { pointee: Box<CType>, is_const: bool },
+    Array { element: Box<CType>, len: ArrayLen },
+    Function { return_type: Box<CType>, params: Vec<CDecl> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArrayLen {
+    Literal(u64),
+    /// An API constant such as `VK_MAX_EXTENSION_NAME_SIZE`.
+    Constant(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CDecl {
+    pub name: Option<String>,
+    pub ty: CType,
+    /// Width of a bitfield member, e.g. `24` for `uint32_t instanceCustomIndex:24`.
+    pub bitfield: Option<u32>,
+}
+
+/// Parses a single declaration such as `const void* const* ppData`, `float blendConstants[4]`
+/// or a whole `typedef void (VKAPI_PTR *PFN_vkVoidFunction)(void);`.
+pub fn parse_declaration(code: &str) -> Result<CDecl, String> {
+    let mut parser = Parser {
+        tokens: tokenize(code)?,
+        pos: 0,
+    };
+    let decl = parser.parse_declaration()?;
+    if parser.peek() == Some(&Token::Semicolon) {
+        parser.pos += 1;
+    }
+    if let Some(token) = parser.peek() {
+        return Err(format!("unexpected {token:?} in `{code}`"));
+    }
+    Ok(decl)
+}
+
+/// Parses an abstract type name without a declarator name, such as `const char*`.
+pub fn parse_type(code: &str) -> Result<CType, String> {
+    let decl = parse_declaration(code)?;
+    if let Some(name) = decl.name {
+        return Err(format!("unexpected declarator name `{name}` in type `{code}`"));
+    }
+    Ok(decl.ty)
+}
+
+impl CType {
+    /// Whether the object of this type is const qualified.
+    pub fn is_const(&self) -> bool {
+        match self {
+            CType::Named { is_const, .. } | CType::Pointer { is_const, .. } => *is_const,
+            CType::Array { element, .. } => element.is_const(),
+            CType::Function { .. } => false,
+        }
+    }
+
+    pub fn is_void(&self) -> bool {
+        matches!(self, CType::Named { name, .. } if name == "void")
+    }
+
+    /// The name of the innermost named type, e.g. `VkBuffer` for `const VkBuffer*`.
+    pub fn base_name(&self) -> &str {
+        match self {
+            CType::Named { name, .. } => name,
+            CType::Pointer { pointee, .. } => pointee.base_name(),
+            CType::Array { element, .. } => element.base_name(),
+            CType::Function { return_type, .. } => return_type.base_name(),
+        }
+    }
+
+    /// The Rust type used for a struct member or a return value.
+    pub fn to_rust(&self) -> String {
+        match self {
+            CType::Named { name, .. } => rust_named_type(name),
+            CType::Pointer { pointee, .. } => match pointee.as_ref() {
+                CType::Function {
+                    return_type,
+                    params,
+                } => format!("Option<{}>", rust_fn_type(return_type, params)),
+                pointee if pointee.is_const() => format!("*const {}", pointee.to_rust()),
+                pointee => format!("*mut {}", pointee.to_rust()),
+            },
+            CType::Array { element, len } => format!("[{}; {}]", element.to_rust(), len.to_rust()),
+            CType::Function {
+                return_type,
+                params,
+            } => rust_fn_type(return_type, params),
+        }
+    }
+
+    /// The Rust type used for a function parameter, where C arrays decay to pointers.
+    pub fn to_rust_param(&self) -> String {
+        match self {
+            CType::Array { element, .. } if element.is_const() => format!("*const {}", self.to_rust()),
+            CType::Array { .. } => format!("*mut {}", self.to_rust()),
+            _ => self.to_rust(),
+        }
+    }
+}
+
+impl ArrayLen {
+    pub fn to_rust(&self) -> String {
+        match self {
+            ArrayLen::Literal(len) => len.to_string(),
+            ArrayLen::Constant(name) => format!("vk::{}", name.strip_prefix("VK_").unwrap_or(name)),
+        }
+    }
+}
+
+impl CDecl {
+    /// The parameter or field name in Rust, following ash's naming.
+    pub fn rust_name(&self) -> String {
+        rust_param_name(self.name.as_deref().unwrap_or("_"))
+    }
+}
+
+pub fn rust_param_name(name: &str) -> String {
+    match name.to_snake_case().as_str() {
+        "type" => "ty".to_string(),
+        name => name.to_string(),
+    }
+}
+
+fn rust_fn_type(return_type: &CType, params: &[CDecl]) -> String {
+    let params = params
+        .iter()
+        .map(|param| format!("{}: {}", param.rust_name(), param.ty.to_rust_param()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if return_type.is_void() {
+        format!("unsafe extern \"system\" fn({params})")
+    } else {
+        format!("unsafe extern \"system\" fn({params}) -> {}", return_type.to_rust())
+    }
+}
+
+fn rust_named_type(name: &str) -> String {
+    match name {
+        "void" => "std::ffi::c_void".to_string(),
+        "char" => "std::ffi::c_char".to_string(),
+        "int" => "std::ffi::c_int".to_string(),
+        "float" => "f32".to_string(),
+        "double" => "f64".to_string(),
+        "uint64_t" => "u64".to_string(),
+        "uint32_t" => "u32".to_string(),
+        "uint16_t" => "u16".to_string(),
+        "uint8_t" => "u8".to_string(),
+        "int64_t" => "i64".to_string(),
+        "int32_t" => "i32".to_string(),
+        "int16_t" => "i16".to_string(),
+        "int8_t" => "i8".to_string(),
+        "size_t" => "usize".to_string(),
+        name if name.starts_with("PFN_") => format!("vk::{name}"),
+        name if name.starts_with("Vk") => format!("vk::{}", &name[2..]),
+        name if name.starts_with("StdVideo") => format!("vk::native::{name}"),
+        // Platform types are re-exported from `vk` by ash.
+        name => format!("vk::{name}"),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(u64),
+    Star,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    Colon,
+    Semicolon,
+}
+
+/// Keywords and macros which carry no type information.
+const IGNORED_IDENTS: &[&str] = &["typedef", "struct", "VKAPI_PTR", "VKAPI_ATTR", "VKAPI_CALL"];
+
+fn tokenize(code: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = code.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '*' => Token::Star,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            ',' => Token::Comma,
+            ':' => Token::Colon,
+            ';' => Token::Semicolon,
+            '/' if chars.peek().map(|(_, c)| *c) == Some('/') => {
+                while chars.next_if(|(_, c)| *c != '\n').is_some() {}
+                continue;
+            }
+            '/' if chars.peek().map(|(_, c)| *c) == Some('*') => {
+                chars.next();
+                let mut previous = ' ';
+                for (_, c) in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+                continue;
+            }
+            c if c.is_ascii_digit() => {
+                let mut end = start + 1;
+                while let Some((i, _)) = chars.next_if(|(_, c)| c.is_ascii_alphanumeric()) {
+                    end = i + 1;
+                }
+                let literal = code[start..end].trim_end_matches(['U', 'u', 'L', 'l']);
+                Token::Number(
+                    literal
+                        .parse()
+                        .map_err(|_| format!("invalid number `{}` in `{code}`", &code[start..end]))?,
+                )
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut end = start + 1;
+                while let Some((i, _)) = chars.next_if(|(_, c)| c.is_ascii_alphanumeric() || *c == '_') {
+                    end = i + 1;
+                }
+                let ident = &code[start..end];
+                if IGNORED_IDENTS.contains(&ident) {
+                    continue;
+                }
+                Token::Ident(ident.to_string())
+            }
+            c => return Err(format!("unexpected character `{c}` in `{code}`")),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+enum Modifier {
+    Pointer { is_const: bool },
+    Array(ArrayLen),
+    Function(Vec<CDecl>),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            token => Err(format!("expected {expected:?}, found {token:?}")),
+        }
+    }
+
+    fn parse_declaration(&mut self) -> Result<CDecl, String> {
+        let base = self.parse_specifiers()?;
+        let (name, modifiers) = self.parse_declarator()?;
+        let bitfield = if self.peek() == Some(&Token::Colon) {
+            self.pos += 1;
+            match self.next() {
+                Some(Token::Number(width)) => Some(width as u32),
+                token => return Err(format!("expected bitfield width, found {token:?}")),
+            }
+        } else {
+            None
+        };
+        let ty = modifiers.into_iter().fold(base, |ty, modifier| match modifier {
+            Modifier::Pointer { is_const } => CType::Pointer {
+                pointee: Box::new(ty),
+                is_const,
+            },
+            Modifier::Array(len) => CType::Array {
+                element: Box::new(ty),
+                len,
+            },
+            Modifier::Function(params) => CType::Function {
+                return_type: Box::new(ty),
+                params,
+            },
+        });
+        Ok(CDecl { name, ty, bitfield })
+    }
+
+    /// Parses the type specifier and its qualifiers, e.g. `const VkBufferCreateInfo`.
+    fn parse_specifiers(&mut self) -> Result<CType, String> {
+        let mut is_const = false;
+        let mut name = None;
+        while let Some(Token::Ident(ident)) = self.peek() {
+            if ident == "const" {
+                is_const = true;
+            } else if name.is_none() {
+                name = Some(ident.clone());
+            } else {
+                break;
+            }
+            self.pos += 1;
+        }
+        let name = name.ok_or_else(|| format!("missing type name, found {:?}", self.peek()))?;
+        Ok(CType::Named { name, is_const })
+    }
+
+    /// Parses a declarator, returning its name and the type modifiers ordered from the
+    /// innermost (applied to the specifier first) to the outermost.
+    fn parse_declarator(&mut self) -> Result<(Option<String>, Vec<Modifier>), String> {
+        let mut modifiers = Vec::new();
+        while self.peek() == Some(&Token::Star) {
+            self.pos += 1;
+            let is_const = self.peek() == Some(&Token::Ident("const".to_string()));
+            if is_const {
+                self.pos += 1;
+            }
+            modifiers.push(Modifier::Pointer { is_const });
+        }
+
+        let (name, inner) = match self.peek() {
+            Some(Token::LParen) if self.tokens.get(self.pos + 1) == Some(&Token::Star) => {
+                self.pos += 1;
+                let inner = self.parse_declarator()?;
+                self.expect(Token::RParen)?;
+                inner
+            }
+            Some(Token::Ident(name)) => {
+                let name = name.clone();
+                self.pos += 1;
+                (Some(name), Vec::new())
+            }
+            _ => (None, Vec::new()),
+        };
+
+        let mut suffixes = Vec::new();
+        loop {
+            match self.peek() {
+                Some(Token::LBracket) => {
+                    self.pos += 1;
+                    let len = match self.next() {
+                        Some(Token::Number(len)) => ArrayLen::Literal(len),
+                        Some(Token::Ident(constant)) => ArrayLen::Constant(constant),
+                        token => return Err(format!("expected array length, found {token:?}")),
+                    };
+                    self.expect(Token::RBracket)?;
+                    suffixes.push(Modifier::Array(len));
+                }
+                Some(Token::LParen) => {
+                    self.pos += 1;
+                    suffixes.push(Modifier::Function(self.parse_params()?));
+                }
+                _ => break,
+            }
+        }
+        // `a[2][3]` is an array of 2 arrays of 3, so the last suffix binds first.
+        modifiers.extend(suffixes.into_iter().rev());
+        modifiers.extend(inner);
+        Ok((name, modifiers))
+    }
+
+    fn parse_params(&mut self) -> Result<Vec<CDecl>, String> {
+        let mut params = Vec::new();
+        if self.peek() == Some(&Token::RParen) {
+            self.pos += 1;
+            return Ok(params);
+        }
+        loop {
+            params.push(self.parse_declaration()?);
+            match self.next() {
+                Some(Token::Comma) => continue,
+                Some(Token::RParen) => break,
+                token => return Err(format!("expected `,` or `)`, found {token:?}")),
+            }
+        }
+        // `(void)` declares a function without parameters.
+        if let [CDecl {
+            name: None,
+            ty: CType::Named { name, .. },
+            ..
+        }] = params.as_slice()
+        {
+            if name == "void" {
+                params.clear();
+            }
+        }
+        Ok(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(name: &str, is_const: bool) -> CType {
+        CType::Named {
+            name: name.to_string(),
+            is_const,
+        }
+    }
+
+    #[test]
+    fn const_pointer_to_const_pointer() {
+        let decl = parse_declaration("const void* const* ppData").unwrap();
+        assert_eq!(decl.name.as_deref(), Some("ppData"));
+        assert_eq!(
+            decl.ty,
+            CType::Pointer {
+                pointee: Box::new(CType::Pointer {
+                    pointee: Box::new(named("void", true)),
+                    is_const: true,
+                }),
+                is_const: false,
+            }
+        );
+    }
+
+    #[test]
+    fn multidimensional_array() {
+        let decl = parse_declaration("float matrix[3][4]").unwrap();
+        assert_eq!(decl.name.as_deref(), Some("matrix"));
+        assert_eq!(
+            decl.ty,
+            CType::Array {
+                element: Box::new(CType::Array {
+                    element: Box::new(named("float", false)),
+                    len: ArrayLen::Literal(4),
+                }),
+                len: ArrayLen::Literal(3),
+            }
+        );
+    }
+
+    #[test]
+    fn bitfield() {
+        let decl = parse_declaration("uint32_t x:24").unwrap();
+        assert_eq!(decl.name.as_deref(), Some("x"));
+        assert_eq!(decl.ty, named("uint32_t", false));
+        assert_eq!(decl.bitfield, Some(24));
+    }
+
+    #[test]
+    fn funcpointer_typedef() {
+        let decl = parse_declaration("typedef void (VKAPI_PTR *PFN_vkVoidFunction)(void);").unwrap();
+        assert_eq!(decl.name.as_deref(), Some("PFN_vkVoidFunction"));
+        assert_eq!(
+            decl.ty,
+            CType::Pointer {
+                pointee: Box::new(CType::Function {
+                    return_type: Box::new(named("void", false)),
+                    params: Vec::new(),
+                }),
+                is_const: false,
+            }
+        );
+    }
+
+    #[test]
+    fn opaque_struct_pointer_typedef() {
+        let decl = parse_declaration("typedef struct __IOSurface* IOSurfaceRef;").unwrap();
+        assert_eq!(decl.name.as_deref(), Some("IOSurfaceRef"));
+        assert_eq!(
+            decl.ty,
+            CType::Pointer {
+                pointee: Box::new(named("__IOSurface", false)),
+                is_const: false,
+            }
+        );
+    }
+
+    #[test]
+    fn objective_c_declaration_is_rejected() {
+        assert!(parse_declaration("typedef __unsafe_unretained id<MTLDevice> MTLDevice_id;").is_err());
+    }
+}
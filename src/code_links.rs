@@ -0,0 +1,64 @@
+//! Links from the identifiers inside generated code tabs to their pages, so that the frontend
+//! can make them clickable without tokenizing C, C++, Rust or Zig itself.
+
+use regex::Regex;
+
+use crate::{backend::CodeBlock, json_string, Converter};
+
+impl Converter {
+    /// Records the links of the blocks rendered for the page being converted.
+    pub(crate) fn record_code_links(&self, blocks: &[CodeBlock]) {
+        let identifier = Regex::new(r"[A-Za-z_][A-Za-z0-9_]*").unwrap();
+        let mut code_links = self.code_links.borrow_mut();
+        for block in blocks {
+            let links = identifier
+                .find_iter(&block.code)
+                .filter_map(|token| {
+                    let target = self.link_target(token.as_str(), block.lang)?;
+                    Some(format!("[{}, {}, {}]", token.start(), token.end(), json_string(&target)))
+                })
+                .collect::<Vec<_>>();
+            let index = code_links.len();
+            code_links.push(format!(
+                "{{\"block\": {index}, \"tab\": {}, \"links\": [{}]}}",
+                json_string(&block.label),
+                links.join(", ")
+            ));
+        }
+    }
+
+    /// The links recorded since the last call, one entry per code block of the page in order.
+    /// Each link is the start and end byte offset of the identifier and the page it names.
+    pub(crate) fn code_links_attributes(&self) -> String {
+        let code_links = self.code_links.take();
+        if code_links.is_empty() {
+            return String::new();
+        }
+        format!("code_links: [{}]\n", code_links.join(", "))
+    }
+
+    /// The page an identifier refers to. Registry names are matched as is in every language,
+    /// while names that bindings strip of their `Vk`, `VK_` or `PFN_vk` prefix are only looked up
+    /// outside of C and XML.
+    fn link_target(&self, token: &str, lang: &str) -> Option<String> {
+        let has_page = |name: &str| {
+            self.types.contains_key(name) || self.commands.contains_key(name) || self.consts.contains_key(name)
+        };
+        if has_page(token) {
+            return Some(token.to_string());
+        }
+        if let Some(command) = token.strip_prefix("PFN_").filter(|command| self.commands.contains_key(*command)) {
+            return Some(command.to_string());
+        }
+        if lang == "c" || lang == "xml" {
+            return None;
+        }
+        let candidates = [
+            format!("Vk{token}"),
+            format!("VK_{token}"),
+            token.strip_prefix("Pfn").map(|name| format!("PFN_vk{name}")).unwrap_or_default(),
+            token.strip_prefix("Pfn").map(|name| format!("vk{name}")).unwrap_or_default(),
+        ];
+        candidates.into_iter().find(|candidate| has_page(candidate))
+    }
+}
@@ -0,0 +1,195 @@
+//! Evaluation of the C expressions used as values of API constants, e.g. `(~0ULL)` or `1000.0F`.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConstValue {
+    U32(u32),
+    U64(u64),
+    I32(i32),
+    F32(f32),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Int(u64),
+    Float(f64),
+    Not(Box<Expr>),
+    Neg(Box<Expr>),
+}
+
+/// Evaluates `expression` as a value of the C type given by the `type` attribute of the constant.
+pub fn evaluate(c_type: &str, expression: &str) -> Result<ConstValue, String> {
+    let (expr, rest) = parse_expr(expression.trim())?;
+    if !rest.trim().is_empty() {
+        return Err(format!("trailing `{rest}` in `{expression}`"));
+    }
+    Ok(match c_type {
+        "uint32_t" => ConstValue::U32(eval_int(&expr, 32)? as u32),
+        "uint64_t" => ConstValue::U64(eval_int(&expr, 64)?),
+        "int32_t" => ConstValue::I32(eval_int(&expr, 32)? as u32 as i32),
+        "float" => ConstValue::F32(eval_float(&expr)? as f32),
+        c_type => return Err(format!("unsupported constant type {c_type}")),
+    })
+}
+
+/// Follows the chain of aliases starting at `name`, `alias_of` giving the constant a constant
+/// aliases, if any.
+pub fn resolve_alias<'a>(name: &'a str, alias_of: impl Fn(&'a str) -> Option<&'a str>) -> &'a str {
+    let mut name = name;
+    while let Some(alias) = alias_of(name) {
+        name = alias;
+    }
+    name
+}
+
+/// The Rust literal ash uses for the constant, keeping complements such as `!0` intact.
+pub fn rust_literal(expression: &str) -> Result<String, String> {
+    let (expr, _) = parse_expr(expression.trim())?;
+    Ok(expr_to_rust(&expr))
+}
+
+impl ConstValue {
+    pub fn rust_type(&self) -> &'static str {
+        match self {
+            ConstValue::U32(_) => "u32",
+            ConstValue::U64(_) => "u64",
+            ConstValue::I32(_) => "i32",
+            ConstValue::F32(_) => "f32",
+        }
+    }
+
+    pub fn decimal(&self) -> String {
+        match self {
+            ConstValue::U32(value) => value.to_string(),
+            ConstValue::U64(value) => value.to_string(),
+            ConstValue::I32(value) => value.to_string(),
+            ConstValue::F32(value) => format!("{value:?}"),
+        }
+    }
+
+    /// Hexadecimal representation padded to the width of the type. Not defined for floats.
+    pub fn hex(&self) -> Option<String> {
+        match self {
+            ConstValue::U32(value) => Some(format!("{value:#010X}")),
+            ConstValue::U64(value) => Some(format!("{value:#018X}")),
+            ConstValue::I32(value) => Some(format!("{:#010X}", *value as u32)),
+            ConstValue::F32(_) => None,
+        }
+    }
+}
+
+fn expr_to_rust(expr: &Expr) -> String {
+    match expr {
+        Expr::Int(value) => value.to_string(),
+        Expr::Float(value) => format!("{value:?}"),
+        Expr::Not(expr) => format!("!{}", expr_to_rust(expr)),
+        Expr::Neg(expr) => format!("-{}", expr_to_rust(expr)),
+    }
+}
+
+fn eval_int(expr: &Expr, bits: u32) -> Result<u64, String> {
+    let mask = if bits == 64 { u64::MAX } else { (1 << bits) - 1 };
+    Ok(match expr {
+        Expr::Int(value) => value & mask,
+        Expr::Float(_) => return Err("floating point value in integer constant".to_string()),
+        Expr::Not(expr) => !eval_int(expr, bits)? & mask,
+        Expr::Neg(expr) => eval_int(expr, bits)?.wrapping_neg() & mask,
+    })
+}
+
+fn eval_float(expr: &Expr) -> Result<f64, String> {
+    Ok(match expr {
+        Expr::Int(value) => *value as f64,
+        Expr::Float(value) => *value,
+        Expr::Not(_) => return Err("bitwise complement of a floating point value".to_string()),
+        Expr::Neg(expr) => -eval_float(expr)?,
+    })
+}
+
+/// Parses a unary expression, returning it along with the unparsed remainder of the input.
+fn parse_expr(input: &str) -> Result<(Expr, &str), String> {
+    let input = input.trim_start();
+    if let Some(rest) = input.strip_prefix('(') {
+        let (expr, rest) = parse_expr(rest)?;
+        let rest = rest
+            .trim_start()
+            .strip_prefix(')')
+            .ok_or_else(|| format!("missing `)` before `{rest}`"))?;
+        return Ok((expr, rest));
+    }
+    if let Some(rest) = input.strip_prefix('~') {
+        let (expr, rest) = parse_expr(rest)?;
+        return Ok((Expr::Not(Box::new(expr)), rest));
+    }
+    if let Some(rest) = input.strip_prefix('-') {
+        let (expr, rest) = parse_expr(rest)?;
+        return Ok((Expr::Neg(Box::new(expr)), rest));
+    }
+    let end = input
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.'))
+        .unwrap_or(input.len());
+    let (literal, rest) = input.split_at(end);
+    let invalid = |e: &dyn std::fmt::Display| format!("invalid literal `{literal}`: {e}");
+    let expr = if let Some(hex) = literal.strip_prefix("0x").or_else(|| literal.strip_prefix("0X")) {
+        let hex = hex.trim_end_matches(['U', 'u', 'L', 'l']);
+        Expr::Int(u64::from_str_radix(hex, 16).map_err(|e| invalid(&e))?)
+    } else if literal.contains('.') || literal.ends_with(['F', 'f']) {
+        let number = literal.trim_end_matches(['F', 'f']);
+        Expr::Float(number.parse().map_err(|e| invalid(&e))?)
+    } else {
+        let number = literal.trim_end_matches(['U', 'u', 'L', 'l']);
+        Expr::Int(number.parse().map_err(|e| invalid(&e))?)
+    };
+    Ok((expr, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn complement_of_zero() {
+        let value = evaluate("uint32_t", "(~0U)").unwrap();
+        assert_eq!(value, ConstValue::U32(u32::MAX));
+        assert_eq!(value.hex().as_deref(), Some("0xFFFFFFFF"));
+        assert_eq!(rust_literal("(~0U)").unwrap(), "!0");
+    }
+
+    #[test]
+    fn complement_of_zero_64_bit() {
+        let value = evaluate("uint64_t", "(~0ULL)").unwrap();
+        assert_eq!(value, ConstValue::U64(u64::MAX));
+        assert_eq!(value.decimal(), "18446744073709551615");
+        assert_eq!(value.hex().as_deref(), Some("0xFFFFFFFFFFFFFFFF"));
+    }
+
+    #[test]
+    fn complement_of_nonzero() {
+        assert_eq!(evaluate("uint32_t", "(~1U)").unwrap(), ConstValue::U32(4294967294));
+        assert_eq!(evaluate("uint32_t", "(~2U)").unwrap(), ConstValue::U32(4294967293));
+        assert_eq!(rust_literal("(~2U)").unwrap(), "!2");
+    }
+
+    #[test]
+    fn float_suffix() {
+        let value = evaluate("float", "1000.0F").unwrap();
+        assert_eq!(value, ConstValue::F32(1000.0));
+        assert_eq!(value.decimal(), "1000.0");
+        assert_eq!(value.hex(), None);
+        assert_eq!(rust_literal("1000.0F").unwrap(), "1000.0");
+    }
+
+    #[test]
+    fn alias_chain() {
+        let aliases = [
+            ("VK_QUEUE_FAMILY_EXTERNAL_KHR", "VK_QUEUE_FAMILY_EXTERNAL"),
+            ("VK_QUEUE_FAMILY_EXTERNAL_ALIAS", "VK_QUEUE_FAMILY_EXTERNAL_KHR"),
+        ];
+        let values = [("VK_QUEUE_FAMILY_EXTERNAL", "(~1U)")];
+        let alias_of = |name: &str| aliases.iter().find(|(alias, _)| *alias == name).map(|(_, target)| *target);
+        let name = resolve_alias("VK_QUEUE_FAMILY_EXTERNAL_ALIAS", alias_of);
+        assert_eq!(name, "VK_QUEUE_FAMILY_EXTERNAL");
+        assert_eq!(resolve_alias(name, alias_of), name);
+        let (_, value) = values.iter().find(|(constant, _)| *constant == name).unwrap();
+        assert_eq!(evaluate("uint32_t", value).unwrap(), ConstValue::U32(4294967294));
+    }
+}
@@ -0,0 +1,171 @@
+use regex::Regex;
+
+use crate::Converter;
+
+/// A `VK_MAKE_API_VERSION` value split into its components.
+pub(crate) struct ApiVersion {
+    pub variant: u32,
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl ApiVersion {
+    pub fn packed(&self) -> u32 {
+        (self.variant << 29) | (self.major << 22) | (self.minor << 12) | self.patch
+    }
+}
+
+impl Converter {
+    pub(crate) fn generate_define(&self, name: &str) -> String {
+        self.render_tabs(|backend| backend.define_blocks(self, name))
+    }
+
+    pub(crate) fn define_attributes(&self, name: &str) -> String {
+        if let Some(version) = self.define_api_version(name) {
+            return format!(
+                "value: {}\napi_version: {{ variant: {}, major: {}, minor: {}, patch: {} }}\n",
+                version.packed(),
+                version.variant,
+                version.major,
+                version.minor,
+                version.patch
+            );
+        }
+        match self.define_number(name) {
+            Some(value) => format!("value: {value}\n"),
+            None => String::new(),
+        }
+    }
+
+    fn define_code(&self, name: &str) -> Option<&str> {
+        match &self.types.get(name)?.spec {
+            vk_parse::TypeSpec::Code(code) => Some(code.code.as_str()),
+            _ => None,
+        }
+    }
+
+    /// The `#define` line of a define. The code may start with a comment, and deprecated defines
+    /// such as `VK_API_VERSION` have their `#define` commented out.
+    fn define_line(&self, name: &str) -> Option<&str> {
+        self.define_code(name)?
+            .lines()
+            .map(str::trim_start)
+            .find(|line| line.starts_with("#define"))
+    }
+
+    /// The value of a define of the form `#define NAME 123`.
+    pub(crate) fn define_number(&self, name: &str) -> Option<u32> {
+        self.define_line(name)?.split_whitespace().nth(2)?.parse().ok()
+    }
+
+    /// Decodes defines built from `VK_MAKE_API_VERSION`, such as `VK_API_VERSION_1_3` or
+    /// `VK_HEADER_VERSION_COMPLETE`.
+    pub(crate) fn define_api_version(&self, name: &str) -> Option<ApiVersion> {
+        let line = self.define_line(name)?;
+        let captures = Regex::new(r"\bVK_MAKE_API_VERSION\(\s*(\w+),\s*(\w+),\s*(\w+),\s*(\w+)\s*\)")
+            .unwrap()
+            .captures(line)?;
+        let argument = |i: usize| {
+            let argument = captures.get(i).unwrap().as_str();
+            argument.parse().ok().or_else(|| self.define_number(argument))
+        };
+        Some(ApiVersion {
+            variant: argument(1)?,
+            major: argument(2)?,
+            minor: argument(3)?,
+            patch: argument(4)?,
+        })
+    }
+
+    /// The ash equivalent of a define, if there is one.
+    pub(crate) fn ash_define(&self, name: &str) -> Option<String> {
+        let code = match name {
+            "VK_MAKE_VERSION" => "#[deprecated = \"This define is deprecated. VK_MAKE_API_VERSION should be used instead.\"]
+pub const fn make_version(major: u32, minor: u32, patch: u32) -> u32 {
+    ((major) << 22) | ((minor) << 12) | (patch)
+}",
+            "VK_VERSION_MAJOR" => "#[deprecated = \"This define is deprecated. VK_API_VERSION_MAJOR should be used instead.\"]
+pub const fn version_major(version: u32) -> u32 {
+    (version) >> 22
+}",
+            "VK_VERSION_MINOR" => "#[deprecated = \"This define is deprecated. VK_API_VERSION_MINOR should be used instead.\"]
+pub const fn version_minor(version: u32) -> u32 {
+    ((version) >> 12) & 0x3ffu32
+}",
+            "VK_VERSION_PATCH" => "#[deprecated = \"This define is deprecated. VK_API_VERSION_PATCH should be used instead.\"]
+pub const fn version_patch(version: u32) -> u32 {
+    (version) & 0xfffu32
+}",
+            "VK_MAKE_API_VERSION" => "pub const fn make_api_version(variant: u32, major: u32, minor: u32, patch: u32) -> u32 {
+    ((variant) << 29) | ((major) << 22) | ((minor) << 12) | (patch)
+}",
+            "VK_API_VERSION_VARIANT" => "pub const fn api_version_variant(version: u32) -> u32 {
+    (version) >> 29
+}",
+            "VK_API_VERSION_MAJOR" => "pub const fn api_version_major(version: u32) -> u32 {
+    ((version) >> 22) & 0x7fu32
+}",
+            "VK_API_VERSION_MINOR" => "pub const fn api_version_minor(version: u32) -> u32 {
+    ((version) >> 12) & 0x3ffu32
+}",
+            "VK_API_VERSION_PATCH" => "pub const fn api_version_patch(version: u32) -> u32 {
+    (version) & 0xfffu32
+}",
+            "VK_DEFINE_HANDLE" => "// Dispatchable handles are pointer sized
+define_handle!(Instance, INSTANCE);
+// expands to
+#[repr(transparent)]
+pub struct Instance(*mut u8);
+impl Handle for Instance {
+    const TYPE: ObjectType = ObjectType::INSTANCE;
+    fn as_raw(self) -> u64 {
+        self.0 as u64
+    }
+    fn from_raw(x: u64) -> Self {
+        Self(x as _)
+    }
+}",
+            "VK_DEFINE_NON_DISPATCHABLE_HANDLE" => "// Non-dispatchable handles are 64 bits on every platform
+handle_nondispatchable!(Buffer, BUFFER);
+// expands to
+#[repr(transparent)]
+pub struct Buffer(u64);
+impl Handle for Buffer {
+    const TYPE: ObjectType = ObjectType::BUFFER;
+    fn as_raw(self) -> u64 {
+        self.0
+    }
+    fn from_raw(x: u64) -> Self {
+        Self(x)
+    }
+}",
+            "VK_NULL_HANDLE" => "// Every handle type has a null constructor
+pub const fn null() -> Self;
+// e.g.
+let buffer = vk::Buffer::null();",
+            _ => "",
+        };
+        if !code.is_empty() {
+            return Some(code.to_string());
+        }
+
+        let rs_name = name.strip_prefix("VK_")?;
+        if let Some(version) = self.define_api_version(name) {
+            let code = self.define_line(name)?;
+            let arguments = Regex::new(r"\bVK_MAKE_API_VERSION\(([^)]*)\)")
+                .unwrap()
+                .captures(code)?
+                .get(1)
+                .unwrap()
+                .as_str()
+                .replace("VK_", "");
+            let packed = version.packed();
+            return Some(format!(
+                "pub const {rs_name}: u32 = make_api_version({arguments}); // {packed} ({packed:#010x})"
+            ));
+        }
+        let value = self.define_number(name)?;
+        Some(format!("pub const {rs_name}: u32 = {value};"))
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{constants, variant_ident, Converter};
+
+/// A value of an enum or a bit of a `FlagBits` enum, either declared with the enum or added by a
+/// feature or extension through `extends`.
+pub(crate) struct EnumValue {
+    pub name: String,
+    pub value: i64,
+    pub bitpos: Option<u32>,
+    pub alias: Option<String>,
+    /// The feature or extension adding the value, `None` for values declared with the enum.
+    pub provided_by: Option<String>,
+}
+
+impl Converter {
+    pub(crate) fn generate_flags(&self, name: &str) -> String {
+        let tabs = self.render_tabs(|backend| backend.flags_blocks(self, name));
+        let Some(bits_name) = self.flag_bits_type(name).filter(|_| self.types[name].alias.is_none()) else {
+            return tabs;
+        };
+        let width = if self.flags_bitwidth(name) == 64 { 18 } else { 10 };
+        let rows = self
+            .enum_values(bits_name)
+            .iter()
+            .map(|bit| {
+                let bitpos = bit.bitpos.map(|bitpos| bitpos.to_string()).unwrap_or_default();
+                let value = match &bit.alias {
+                    Some(alias) => format!("[{alias}](#{alias})"),
+                    None => format!("`{:#0width$X}`", bit.value),
+                };
+                let provided_by = bit.provided_by.as_deref().unwrap_or("");
+                format!(
+                    "| :anchor{{id=\"{}\"}}`{}` | {bitpos} | {value} | {provided_by} |",
+                    bit.name, bit.name
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            "{tabs}
+
+| Bit | Position | Value | Provided by |
+| --- | -------- | ----- | ----------- |
+{rows}"
+        )
+    }
+
+    /// ash's flags type: the `FlagBits` enum as generated by ash, followed by the bits added by
+    /// features and extensions, which ash emits in separate `impl` blocks.
+    pub(crate) fn ash_flags_items(&self, name: &str) -> Vec<syn::Item> {
+        let Some(bits_name) = self.flag_bits_type(name) else {
+            return self.ash.bitmask_items(name);
+        };
+        let bits = self.enum_values(bits_name);
+        let mut rs_items = self.ash.enum_items(&self.enums[bits_name]);
+        let mut providers = Vec::new();
+        for bit in bits.iter() {
+            if let Some(provider) = &bit.provided_by {
+                if !providers.contains(&provider) {
+                    providers.push(provider);
+                }
+            }
+        }
+        for provider in providers {
+            let constants = bits
+                .iter()
+                .filter(|bit| bit.provided_by.as_ref() == Some(provider))
+                .map(|bit| {
+                    let alias = bit.alias.as_ref().map(|alias| variant_ident(bits_name, alias));
+                    (variant_ident(bits_name, &bit.name), bit.value as u64, alias)
+                })
+                .collect::<Vec<_>>();
+            rs_items.extend(self.ash.flag_constant_items(name, provider, &constants));
+        }
+        rs_items
+    }
+
+    pub(crate) fn flags_attributes(&self, name: &str) -> String {
+        let Some(bits_name) = self.flag_bits_type(name) else {
+            return format!("bitwidth: {}\n", self.flags_bitwidth(name));
+        };
+        let bits = self
+            .enum_values(bits_name)
+            .iter()
+            .map(|bit| bit.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "flag_bits: {bits_name}\nbitwidth: {}\nbits: [{bits}]\n",
+            self.flags_bitwidth(name)
+        )
+    }
+
+    /// The `FlagBits` enum named by the `requires` or, for 64-bit flags, `bitvalues` attribute.
+    pub(crate) fn flag_bits_type(&self, name: &str) -> Option<&str> {
+        let ty = self.types.get(self.resolve_type_alias(name))?;
+        let bits = ty.requires.as_ref().or(ty.bitvalues.as_ref())?;
+        self.enums.contains_key(bits).then_some(bits.as_str())
+    }
+
+    /// The flags type whose bits an enum defines, the reverse of [`Converter::flag_bits_type`].
+    pub(crate) fn flags_type_of(&self, bits_name: &str) -> Option<&str> {
+        self.types
+            .iter()
+            .filter(|(_, ty)| ty.category.as_deref() == Some("bitmask") && ty.alias.is_none())
+            .find(|(_, ty)| ty.requires.as_ref().or(ty.bitvalues.as_ref()).map(String::as_str) == Some(bits_name))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// 64 for flags declared as `VkFlags64`, 32 otherwise.
+    pub(crate) fn flags_bitwidth(&self, name: &str) -> u32 {
+        match &self.types[self.resolve_type_alias(name)].spec {
+            vk_parse::TypeSpec::Code(code)
+                if code
+                    .markup
+                    .iter()
+                    .any(|markup| matches!(markup, vk_parse::TypeCodeMarkup::Type(ty) if ty == "VkFlags64")) =>
+            {
+                64
+            }
+            _ => 32,
+        }
+    }
+
+    /// Every value of an enum, or bit of a `FlagBits` enum, followed by those added by features
+    /// and extensions in registry order.
+    pub(crate) fn enum_values(&self, enum_name: &str) -> Vec<EnumValue> {
+        let is_bitmask = self.enums[enum_name].kind.as_deref() == Some("bitmask");
+        let mut values = Vec::new();
+        for child in self.enums[enum_name].children.iter() {
+            let vk_parse::EnumsChild::Enum(e) = child else {
+                continue;
+            };
+            if e.deprecated.is_some() {
+                continue;
+            }
+            values.push(enum_value(e, is_bitmask, None, None));
+        }
+
+        for child in self.registry.0.iter() {
+            let (provider, requires) = match child {
+                vk_parse::RegistryChild::Feature(feature) if feature.name != "VKSC_VERSION_1_0" => {
+                    (&feature.name, &feature.children)
+                }
+                vk_parse::RegistryChild::Extensions(extensions) => {
+                    for extension in extensions.children.iter() {
+                        let supported = extension.supported.as_deref().unwrap_or("vulkan");
+                        if !supported.split(',').any(|api| api == "vulkan") {
+                            continue;
+                        }
+                        self.push_extending_values(
+                            &mut values,
+                            enum_name,
+                            is_bitmask,
+                            (&extension.name, extension.number),
+                            &extension.children,
+                        );
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+            self.push_extending_values(&mut values, enum_name, is_bitmask, (provider, None), requires);
+        }
+
+        // Resolve the values of aliases now that every value is known.
+        for i in 0..values.len() {
+            let target = values[i]
+                .alias
+                .as_ref()
+                .and_then(|alias| values.iter().find(|value| &value.name == alias))
+                .map(|target| (target.value, target.bitpos));
+            if let Some((value, bitpos)) = target {
+                values[i].value = value;
+                values[i].bitpos = bitpos;
+            }
+        }
+        values
+    }
+
+    /// Appends the values `requires` adds to `enum_name`. `provider` is the name of the feature or
+    /// extension, along with the extension number `offset` values are relative to by default.
+    fn push_extending_values(
+        &self,
+        values: &mut Vec<EnumValue>,
+        enum_name: &str,
+        is_bitmask: bool,
+        provider: (&str, Option<i64>),
+        requires: &[vk_parse::ExtensionChild],
+    ) {
+        let (provider, number) = provider;
+        for require in requires.iter() {
+            let vk_parse::ExtensionChild::Require { api, items, .. } = require else {
+                continue;
+            };
+            if api.as_deref().map_or(false, |api| !api.split(',').any(|api| api == "vulkan")) {
+                continue;
+            }
+            for item in items.iter() {
+                let vk_parse::InterfaceItem::Enum(e) = item else {
+                    continue;
+                };
+                let extends = match &e.spec {
+                    vk_parse::EnumSpec::Alias { extends, .. }
+                    | vk_parse::EnumSpec::Bitpos { extends, .. }
+                    | vk_parse::EnumSpec::Value { extends, .. } => extends.as_deref(),
+                    vk_parse::EnumSpec::Offset { extends, .. } => Some(extends.as_str()),
+                    _ => None,
+                };
+                if extends != Some(enum_name) || values.iter().any(|value| value.name == e.name) {
+                    continue;
+                }
+                values.push(enum_value(e, is_bitmask, Some(provider), number));
+            }
+        }
+    }
+}
+
+/// `extnumber` is the number of the extension requiring `e`, which `offset` values are relative
+/// to unless they give their own.
+fn enum_value(e: &vk_parse::Enum, is_bitmask: bool, provided_by: Option<&str>, extnumber: Option<i64>) -> EnumValue {
+    let mut value = EnumValue {
+        name: e.name.clone(),
+        value: 0,
+        bitpos: None,
+        alias: None,
+        provided_by: provided_by.map(str::to_string),
+    };
+    match &e.spec {
+        vk_parse::EnumSpec::Bitpos { bitpos, .. } => {
+            value.bitpos = Some(*bitpos as u32);
+            value.value = 1 << bitpos;
+        }
+        vk_parse::EnumSpec::Value { value: expression, .. } => {
+            let c_type = if is_bitmask { "uint64_t" } else { "int32_t" };
+            value.value = match constants::evaluate(c_type, expression).unwrap() {
+                constants::ConstValue::U64(bits) => bits as i64,
+                constants::ConstValue::I32(number) => number as i64,
+                _ => unreachable!(),
+            };
+        }
+        // The extension enum numbering scheme of the Vulkan style guide.
+        vk_parse::EnumSpec::Offset {
+            offset,
+            extnumber: own_number,
+            dir,
+            ..
+        } => {
+            let number = own_number.or(extnumber).unwrap();
+            let magnitude = 1_000_000_000 + (number - 1) * 1000 + offset;
+            value.value = if *dir { magnitude } else { -magnitude };
+        }
+        vk_parse::EnumSpec::Alias { alias, .. } => value.alias = Some(alias.clone()),
+        _ => unimplemented!(),
+    }
+    value
+}
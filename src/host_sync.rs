@@ -0,0 +1,206 @@
+use std::{collections::BTreeMap, fs::File, io::Write};
+
+use regex::Regex;
+
+use crate::{json_string, Converter};
+
+impl Converter {
+    /// Host synchronization statements for the parameters of a command, derived from the
+    /// `externsync` attributes in the same way makeSpec builds its `hostsynctable` includes.
+    pub(crate) fn explicit_host_sync(&self, command: &vk_parse::CommandDefinition) -> Vec<String> {
+        command
+            .params
+            .iter()
+            .filter(|param| param.api.as_ref().map(String::as_str) != Some("vulkansc"))
+            .filter_map(|param| Some((param, param.externsync.as_ref()?)))
+            .flat_map(|(param, externsync)| {
+                let name = param.definition.name.as_str();
+                let is_array = param.len.is_some();
+                externsync.split(',').map(move |expression| {
+                    let expression = expression.trim();
+                    if expression == "true" {
+                        if is_array {
+                            format!("Host access to each member of `{name}` must be externally synchronized")
+                        } else {
+                            format!("Host access to `{name}` must be externally synchronized")
+                        }
+                    } else if let Some(expression) = expression.strip_prefix("maybe:") {
+                        format!("Host access to `{expression}` may need to be externally synchronized")
+                    } else {
+                        format!("Host access to `{expression}` must be externally synchronized")
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Implicitly synchronized objects listed under `implicitexternsyncparams`.
+    pub(crate) fn implicit_host_sync(&self, command: &vk_parse::CommandDefinition) -> Vec<String> {
+        command
+            .implicitexternsyncparams
+            .iter()
+            .map(|param| format!("Host access to {} must be externally synchronized", inline_macros(param)))
+            .collect()
+    }
+
+    /// The lists of the `hostsynctable` includes of the fundamentals chapter: `parameters`,
+    /// `parameterlists` or `implicit`.
+    pub(crate) fn host_sync_table(&self, table: &str) -> Option<String> {
+        if !matches!(table, "parameters" | "parameterlists" | "implicit") {
+            return None;
+        }
+        let mut commands = self
+            .commands
+            .values()
+            .filter_map(|command| match command {
+                vk_parse::Command::Definition(command) => Some(command),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        commands.sort_by(|a, b| a.proto.name.cmp(&b.proto.name));
+
+        let mut items = Vec::new();
+        for command in commands {
+            let name = command.proto.name.as_str();
+            let link = format!("[{name}](/man/{name})");
+            if table == "implicit" {
+                for param in command.implicitexternsyncparams.iter() {
+                    items.push(format!("- {} in {link}", inline_macros(param)));
+                }
+                continue;
+            }
+            for param in command.params.iter() {
+                if param.api.as_ref().map(String::as_str) == Some("vulkansc") {
+                    continue;
+                }
+                let Some(externsync) = &param.externsync else {
+                    continue;
+                };
+                let param_name = param.definition.name.as_str();
+                for expression in externsync.split(',').map(str::trim) {
+                    let item = match (table, expression) {
+                        ("parameters", "true") if param.len.is_none() => {
+                            format!("- The `{param_name}` parameter in {link}")
+                        }
+                        ("parameterlists", "true") if param.len.is_some() => {
+                            format!("- Each element of the `{param_name}` parameter in {link}")
+                        }
+                        ("parameterlists", expression) if expression != "true" => {
+                            let expression = expression.strip_prefix("maybe:").unwrap_or(expression);
+                            format!("- `{expression}` in {link}")
+                        }
+                        _ => continue,
+                    };
+                    items.push(item);
+                }
+            }
+        }
+        Some(items.join("\n") + "\n")
+    }
+
+    pub(crate) fn command_host_sync_attributes(&self, command: &vk_parse::CommandDefinition) -> String {
+        let mut attributes = String::new();
+        let explicit = self.explicit_host_sync(command);
+        if !explicit.is_empty() {
+            attributes += &format!("host_sync: [{}]\n", quoted_list(&explicit));
+        }
+        let implicit = self.implicit_host_sync(command);
+        if !implicit.is_empty() {
+            attributes += &format!("implicit_host_sync: [{}]\n", quoted_list(&implicit));
+        }
+        attributes
+    }
+
+    /// Members of a struct declared with `externsync`.
+    pub(crate) fn externsync_members(&self, name: &str) -> Vec<String> {
+        let Some(vk_parse::TypeSpec::Members(members)) = self.types.get(name).map(|ty| &ty.spec) else {
+            return Vec::new();
+        };
+        members
+            .iter()
+            .filter_map(|member| match member {
+                vk_parse::TypeMember::Definition(def) if def.externsync.is_some() => def
+                    .markup
+                    .iter()
+                    .find_map(|markup| match markup {
+                        vk_parse::TypeMemberMarkup::Name(name) => Some(name.clone()),
+                        _ => None,
+                    }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub(crate) fn struct_host_sync_attributes(&self, name: &str) -> String {
+        let members = self.externsync_members(name);
+        if members.is_empty() {
+            return String::new();
+        }
+        format!("externsync_members: [{}]\n", members.join(", "))
+    }
+
+    /// Writes every externally synchronized parameter and struct member in the API to
+    /// `./dist/hostsync.json`.
+    pub(crate) fn write_host_sync_listing(&self) {
+        let mut commands = BTreeMap::new();
+        for command in self.commands.values() {
+            let vk_parse::Command::Definition(command) = command else {
+                continue;
+            };
+            let explicit = self.explicit_host_sync(command);
+            let implicit = self.implicit_host_sync(command);
+            if explicit.is_empty() && implicit.is_empty() {
+                continue;
+            }
+            commands.insert(
+                command.proto.name.as_str(),
+                format!(
+                    "{{ \"explicit\": [{}], \"implicit\": [{}] }}",
+                    quoted_list(&explicit),
+                    quoted_list(&implicit)
+                ),
+            );
+        }
+        let mut structs = BTreeMap::new();
+        for name in self.types.keys() {
+            let members = self.externsync_members(name);
+            if !members.is_empty() {
+                structs.insert(name.as_str(), quoted_list(&members));
+            }
+        }
+
+        let commands = commands
+            .iter()
+            .map(|(name, entry)| format!("\"{name}\": {entry}"))
+            .collect::<Vec<_>>()
+            .join(",\n        ");
+        let structs = structs
+            .iter()
+            .map(|(name, members)| format!("\"{name}\": [{members}]"))
+            .collect::<Vec<_>>()
+            .join(",\n        ");
+        let mut file = File::create("./dist/hostsync.json").unwrap();
+        file.write_fmt(format_args!("{{
+    \"commands\": {{
+        {commands}
+    }},
+    \"structs\": {{
+        {structs}
+    }}
+}}")).unwrap();
+    }
+}
+
+fn quoted_list(items: &[String]) -> String {
+    items
+        .iter()
+        .map(|item| json_string(item))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Turns the `pname:` style macros of registry text into inline code.
+fn inline_macros(text: &str) -> String {
+    let macros = Regex::new(r"\b[a-z]name:(\w+)").unwrap();
+    macros.replace_all(text.trim(), "`$1`").into_owned()
+}
@@ -0,0 +1,302 @@
+//! The `{generated}` includes other than the API declarations. makeSpec output is inlined by the
+//! AsciiDoc stage when it exists; the rest reach this stage as links and are generated from vk.xml
+//! where it holds the data, or else listed in `./dist/unresolved_includes.json`.
+
+use std::{fs::File, io::Write};
+
+use crate::{json_string, Converter};
+
+impl Converter {
+    /// Markdown for a generated include, `path` being relative to the generated directory.
+    pub(crate) fn generate_include(&self, path: &str) -> Option<String> {
+        let path = path.strip_suffix(".adoc")?;
+        if let Some(table) = path.strip_prefix("/hostsynctable/") {
+            return self.host_sync_table(table);
+        }
+        if let Some(name) = path
+            .strip_prefix("/validity/protos/")
+            .or_else(|| path.strip_prefix("/validity/structs/"))
+        {
+            return Some(self.generate_validity(name));
+        }
+        if let Some(version) = path.strip_prefix("/meta/promoted_extensions_") {
+            return Some(self.promoted_extensions(version));
+        }
+        match path {
+            "/sync/supportedPipelineStages" => Some(self.supported_pipeline_stages()),
+            "/sync/supportedAccessTypes" => Some(self.supported_access_types()),
+            "/sync/pipelineOrdering" => Some(self.pipeline_ordering()),
+            "/formats/compatibility" => Some(self.format_compatibility()),
+            "/formats/planeformat" => Some(self.plane_formats()),
+            "/formats/spirvimageformat" => Some(self.spirv_image_formats()),
+            "/spirvcap/captable" => Some(self.spirv_table("SPIR-V Capability", |child| match child {
+                vk_parse::RegistryChild::SpirvCapabilities(capabilities) => Some(
+                    capabilities
+                        .children
+                        .iter()
+                        .map(|capability| (capability.name.as_str(), capability.enables.as_slice()))
+                        .collect(),
+                ),
+                _ => None,
+            })),
+            "/spirvcap/exttable" => Some(self.spirv_table("SPIR-V Extension", |child| match child {
+                vk_parse::RegistryChild::SpirvExtensions(extensions) => Some(
+                    extensions
+                        .children
+                        .iter()
+                        .map(|extension| (extension.name.as_str(), extension.enables.as_slice()))
+                        .collect(),
+                ),
+                _ => None,
+            })),
+            _ => None,
+        }
+    }
+
+    /// Records an include that could not be resolved on the page being converted.
+    pub(crate) fn record_unresolved_include(&self, path: &str, page: &str) {
+        self.unresolved_includes
+            .borrow_mut()
+            .entry(path.to_string())
+            .or_default()
+            .insert(page.to_string());
+    }
+
+    /// Writes the unresolved includes with the pages including them to
+    /// `./dist/unresolved_includes.json`.
+    pub(crate) fn write_unresolved_includes(&self) {
+        let entries = self
+            .unresolved_includes
+            .borrow()
+            .iter()
+            .map(|(path, pages)| {
+                let pages = pages.iter().map(|page| json_string(page)).collect::<Vec<_>>().join(", ");
+                format!("{}: [{pages}]", json_string(path))
+            })
+            .collect::<Vec<_>>()
+            .join(",\n    ");
+        let mut file = File::create("./dist/unresolved_includes.json").unwrap();
+        file.write_fmt(format_args!("{{\n    {entries}\n}}\n")).unwrap();
+    }
+
+    /// The extensions promoted to a core version, as listed in the version appendices.
+    fn promoted_extensions(&self, version: &str) -> String {
+        let mut extensions = self
+            .extensions
+            .values()
+            .filter(|ext| ext.promotedto.as_deref() == Some(version))
+            .filter(|ext| {
+                ext.supported
+                    .as_deref()
+                    .map_or(false, |supported| supported.split(',').any(|api| api == "vulkan"))
+            })
+            .map(|ext| ext.name.as_str())
+            .collect::<Vec<_>>();
+        extensions.sort();
+        extensions
+            .iter()
+            .map(|name| format!("- [{name}](/extensions/{name})\n"))
+            .collect()
+    }
+
+    fn sync_children(&self) -> impl Iterator<Item = &vk_parse::SyncChild> {
+        self.registry
+            .0
+            .iter()
+            .filter_map(|child| match child {
+                vk_parse::RegistryChild::Sync(sync) => Some(sync.children.iter()),
+                _ => None,
+            })
+            .flatten()
+    }
+
+    fn formats(&self) -> impl Iterator<Item = &vk_parse::Format> {
+        self.registry
+            .0
+            .iter()
+            .filter_map(|child| match child {
+                vk_parse::RegistryChild::Formats(formats) => Some(formats.children.iter()),
+                _ => None,
+            })
+            .flatten()
+    }
+
+    /// The queue capabilities each pipeline stage of `<syncstage>` requires.
+    fn supported_pipeline_stages(&self) -> String {
+        let mut table = "| Pipeline stage | Required queue capability flag |\n| --- | --- |\n".to_string();
+        for child in self.sync_children() {
+            let vk_parse::SyncChild::Stage(stage) = child else {
+                continue;
+            };
+            if stage.alias.is_some() {
+                continue;
+            }
+            let queues = stage
+                .syncsupport
+                .as_ref()
+                .and_then(|support| support.queues.as_deref())
+                .map_or("None required".to_string(), |queues| code_list(queues, " or "));
+            table += &format!("| `{}` | {queues} |\n", stage.name);
+        }
+        table
+    }
+
+    /// The pipeline stages each access flag of `<syncaccess>` may be used with.
+    fn supported_access_types(&self) -> String {
+        let mut table = "| Access flag | Supported pipeline stages |\n| --- | --- |\n".to_string();
+        for child in self.sync_children() {
+            let vk_parse::SyncChild::Access(access) = child else {
+                continue;
+            };
+            if access.alias.is_some() {
+                continue;
+            }
+            let stages = access
+                .syncsupport
+                .as_ref()
+                .and_then(|support| support.stage.as_deref())
+                .map_or("Any".to_string(), |stages| code_list(stages, ", "));
+            table += &format!("| `{}` | {stages} |\n", access.name);
+        }
+        table
+    }
+
+    /// The logical order of the stages of each pipeline type of `<syncpipeline>`.
+    fn pipeline_ordering(&self) -> String {
+        let mut markdown = String::new();
+        for child in self.sync_children() {
+            let vk_parse::SyncChild::Pipeline(pipeline) = child else {
+                continue;
+            };
+            let depends = pipeline
+                .depends
+                .as_deref()
+                .map(|depends| format!(" (requires {})", depends.replace(',', ", ")))
+                .unwrap_or_default();
+            markdown += &format!("\n{}{depends}:\n\n", pipeline.name);
+            for stage in pipeline.children.iter() {
+                let order = match stage.order.as_deref() {
+                    Some("None") => " (not ordered with the other stages)".to_string(),
+                    _ => String::new(),
+                };
+                let before = stage.before.as_deref().map(|before| format!(" (before `{before}`)")).unwrap_or_default();
+                let after = stage.after.as_deref().map(|after| format!(" (after `{after}`)")).unwrap_or_default();
+                markdown += &format!("- `{}`{order}{before}{after}\n", stage.text);
+            }
+        }
+        markdown
+    }
+
+    /// The format compatibility classes, with the formats of each class.
+    fn format_compatibility(&self) -> String {
+        let mut classes: Vec<(&str, &vk_parse::Format, Vec<&str>)> = Vec::new();
+        for format in self.formats() {
+            match classes.iter_mut().find(|(class, _, _)| *class == format.class) {
+                Some((_, _, formats)) => formats.push(format.name.as_str()),
+                None => classes.push((format.class.as_str(), format, vec![format.name.as_str()])),
+            }
+        }
+        let mut table = "| Class | Block size | Texels per block | Formats |\n| --- | --- | --- | --- |\n".to_string();
+        for (class, format, formats) in classes {
+            let formats = formats.iter().map(|name| format!("`{name}`")).collect::<Vec<_>>().join("<br>");
+            table += &format!(
+                "| {class} | {} byte | {} | {formats} |\n",
+                format.blockSize, format.texelsPerBlock
+            );
+        }
+        table
+    }
+
+    /// The single plane format each plane of a multi-planar format is compatible with.
+    fn plane_formats(&self) -> String {
+        let mut table =
+            "| Format | Plane | Compatible format | Width divisor | Height divisor |\n| --- | --- | --- | --- | --- |\n"
+                .to_string();
+        for format in self.formats() {
+            for child in format.children.iter() {
+                if let vk_parse::FormatChild::Plane {
+                    index,
+                    widthDivisor: width_divisor,
+                    heightDivisor: height_divisor,
+                    compatible,
+                    ..
+                } = child
+                {
+                    table += &format!(
+                        "| `{}` | {index} | `{compatible}` | {width_divisor} | {height_divisor} |\n",
+                        format.name
+                    );
+                }
+            }
+        }
+        table
+    }
+
+    /// The Vulkan format matching each SPIR-V `Image Format`.
+    fn spirv_image_formats(&self) -> String {
+        let mut table = "| SPIR-V Image Format | Compatible Vulkan Format |\n| --- | --- |\n".to_string();
+        for format in self.formats() {
+            for child in format.children.iter() {
+                if let vk_parse::FormatChild::SpirvImageFormat { name, .. } = child {
+                    table += &format!("| `{name}` | `{}` |\n", format.name);
+                }
+            }
+        }
+        table
+    }
+
+    /// A table of the SPIR-V capabilities or extensions of vk.xml with the Vulkan versions,
+    /// extensions, features or properties enabling each of them.
+    fn spirv_table<'a>(
+        &'a self,
+        heading: &str,
+        entries: impl Fn(&'a vk_parse::RegistryChild) -> Option<Vec<(&'a str, &'a [vk_parse::Enable])>>,
+    ) -> String {
+        let mut table = format!("| {heading} | Enabled by |\n| --- | --- |\n");
+        for (name, enables) in self.registry.0.iter().filter_map(entries).flatten() {
+            let enables = enables.iter().map(spirv_enable).collect::<Vec<_>>().join("<br>");
+            table += &format!("| `{name}` | {enables} |\n");
+        }
+        table
+    }
+}
+
+/// A comma separated list of registry names as inline code joined by `separator`.
+fn code_list(names: &str, separator: &str) -> String {
+    names
+        .split(',')
+        .map(|name| format!("`{}`", name.trim()))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn spirv_enable(enable: &vk_parse::Enable) -> String {
+    let requires = |requires: &Option<String>| match requires {
+        Some(requires) => format!(" (requires {})", requires.replace(',', ", ")),
+        None => String::new(),
+    };
+    match enable {
+        vk_parse::Enable::Version(version) => match version.strip_prefix("VK_VERSION_") {
+            Some(number) => format!("Vulkan {}", number.replace('_', ".")),
+            None => format!("`{version}`"),
+        },
+        vk_parse::Enable::Extension(extension) => format!("[{extension}](/extensions/{extension})"),
+        vk_parse::Enable::Feature(feature) => format!(
+            "[{0}](/man/{0})::`{1}`{2}",
+            feature.struct_,
+            feature.feature,
+            requires(&feature.requires)
+        ),
+        vk_parse::Enable::Property(property) => {
+            let relation = if property.value.ends_with("_BIT") { "includes" } else { "is" };
+            format!(
+                "[{0}](/man/{0})::`{1}` {relation} `{2}`{3}",
+                property.property,
+                property.member,
+                property.value,
+                requires(&property.requires)
+            )
+        }
+        _ => unimplemented!(),
+    }
+}
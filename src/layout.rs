@@ -0,0 +1,305 @@
+//! C layout of structs and unions, following the usual `repr(C)` rules: members are placed at
+//! the next multiple of their alignment, and the size is rounded up to the largest alignment.
+
+use crate::{c_decl, constants::ConstValue, Converter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Target {
+    /// LP64 and LLP64 targets such as x86_64 and aarch64.
+    Bits64,
+    /// 32-bit targets with 8-byte aligned 64-bit integers, such as Windows x86 and armv7.
+    /// Non-dispatchable handles are `uint64_t` rather than pointers there.
+    Bits32,
+}
+
+impl Target {
+    fn pointer_size(self) -> u64 {
+        match self {
+            Target::Bits64 => 8,
+            Target::Bits32 => 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Layout {
+    pub size: u64,
+    pub align: u64,
+    pub members: Vec<MemberLayout>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MemberLayout {
+    pub name: String,
+    pub offset: u64,
+    pub size: u64,
+    /// Offset and width in bits inside the storage unit, for bitfield members.
+    pub bitfield: Option<(u32, u32)>,
+}
+
+impl Converter {
+    /// Layout of a struct or union, or `None` if a member has a type of unknown size such as
+    /// the `StdVideo*` types from the video headers.
+    pub(crate) fn type_layout(&self, name: &str, target: Target) -> Option<Layout> {
+        let ty = self.types.get(self.resolve_type_alias(name))?;
+        let vk_parse::TypeSpec::Members(members) = &ty.spec else {
+            return None;
+        };
+        let is_union = ty.category.as_deref() == Some("union");
+        let mut placed = Vec::new();
+        for member in members.iter() {
+            let vk_parse::TypeMember::Definition(def) = member else {
+                continue;
+            };
+            if def.api.as_deref().map_or(false, |api| api != "vulkan") {
+                continue;
+            }
+            let decl = c_decl::parse_declaration(&def.code).ok()?;
+            let size_align = self.c_type_layout(&decl.ty, target)?;
+            placed.push((decl.name.unwrap_or_default(), size_align, decl.bitfield));
+        }
+        Some(place_members(placed, is_union))
+    }
+
+    /// Size and alignment of a C type.
+    fn c_type_layout(&self, ty: &c_decl::CType, target: Target) -> Option<(u64, u64)> {
+        let pointer = (target.pointer_size(), target.pointer_size());
+        match ty {
+            c_decl::CType::Named { name, .. } => self.named_type_layout(name, target),
+            c_decl::CType::Pointer { .. } | c_decl::CType::Function { .. } => Some(pointer),
+            c_decl::CType::Array { element, len } => {
+                let (size, align) = self.c_type_layout(element, target)?;
+                let len = match len {
+                    c_decl::ArrayLen::Literal(len) => *len,
+                    c_decl::ArrayLen::Constant(name) => match self.evaluate_constant(name).1 {
+                        ConstValue::U32(len) => len as u64,
+                        _ => return None,
+                    },
+                };
+                Some((size * len, align))
+            }
+        }
+    }
+
+    fn named_type_layout(&self, name: &str, target: Target) -> Option<(u64, u64)> {
+        let pointer = (target.pointer_size(), target.pointer_size());
+        if let Some(layout) = primitive_layout(name, target) {
+            return Some(layout);
+        }
+        let name = self.resolve_type_alias(name);
+        let ty = self.types.get(name)?;
+        match ty.category.as_deref()? {
+            "struct" | "union" => self.type_layout(name, target).map(|layout| (layout.size, layout.align)),
+            "enum" => Some((4, 4)),
+            "funcpointer" => Some(pointer),
+            // VK_DEFINE_NON_DISPATCHABLE_HANDLE is only a pointer on 64-bit platforms.
+            "handle" if self.is_dispatchable_handle(name) || target == Target::Bits64 => Some(pointer),
+            "handle" => Some((8, 8)),
+            "basetype" | "bitmask" => {
+                let vk_parse::TypeSpec::Code(code) = &ty.spec else {
+                    return None;
+                };
+                // Prefer the plain C typedef after any `#ifdef __OBJC__` variant.
+                let typedef = code.code.lines().rev().find(|line| line.trim_start().starts_with("typedef"))?;
+                let decl = c_decl::parse_declaration(typedef).ok()?;
+                self.c_type_layout(&decl.ty, target)
+            }
+            _ => None,
+        }
+    }
+
+    /// The layout on the reference 64-bit target, with the 32-bit layout added when it differs.
+    pub(crate) fn layout_attributes(&self, name: &str) -> String {
+        if self.types[name].alias.is_some() {
+            return String::new();
+        }
+        let Some(layout) = self.type_layout(name, Target::Bits64) else {
+            return String::new();
+        };
+        let mut json = layout_json(&layout);
+        if let Some(layout_32) = self.type_layout(name, Target::Bits32) {
+            if layout_32 != layout {
+                json.pop();
+                json += &format!(", \"32bit\": {}}}", layout_json(&layout_32));
+            }
+        }
+        format!("layout: {json}\n")
+    }
+}
+
+/// Size and alignment of the C and platform types that vk.xml does not define.
+fn primitive_layout(name: &str, target: Target) -> Option<(u64, u64)> {
+    let pointer = (target.pointer_size(), target.pointer_size());
+    Some(match name {
+        "char" | "uint8_t" | "int8_t" => (1, 1),
+        "uint16_t" | "int16_t" => (2, 2),
+        "uint32_t" | "int32_t" | "int" | "float" => (4, 4),
+        "uint64_t" | "int64_t" | "double" => (8, 8),
+        "size_t" => pointer,
+        // Platform types used by value in structs.
+        "HANDLE" | "HINSTANCE" | "HWND" | "HMONITOR" | "LPCWSTR" | "IOSurfaceRef" | "MTLBuffer_id"
+        | "MTLCommandQueue_id" | "MTLDevice_id" | "MTLSharedEvent_id" | "MTLTexture_id" => pointer,
+        "DWORD" | "xcb_window_t" | "xcb_visualid_t" | "zx_handle_t" | "GgpStreamDescriptor" => (4, 4),
+        "GgpFrameToken" => (8, 8),
+        _ => return None,
+    })
+}
+
+/// Places members given with the size and alignment of their type and their bitfield width.
+/// Consecutive bitfields share a storage unit of their type while their bits fit in it.
+fn place_members(members: Vec<(String, (u64, u64), Option<u32>)>, is_union: bool) -> Layout {
+    let mut layout = Layout {
+        size: 0,
+        align: 1,
+        members: Vec::new(),
+    };
+    // Offset, size and bits used of the storage unit being filled by bitfield members.
+    let mut unit: Option<(u64, u64, u32)> = None;
+    for (name, (size, align), bitfield) in members {
+        layout.align = layout.align.max(align);
+
+        if let Some(width) = bitfield {
+            let fits = matches!(unit, Some((_, unit_size, used)) if unit_size == size && used + width <= size as u32 * 8);
+            let (offset, bit_offset) = if fits {
+                let (offset, _, used) = unit.as_mut().unwrap();
+                let bit_offset = *used;
+                *used += width;
+                (*offset, bit_offset)
+            } else {
+                let offset = align_to(layout.size, align);
+                layout.size = offset + size;
+                unit = Some((offset, size, width));
+                (offset, 0)
+            };
+            layout.members.push(MemberLayout {
+                name,
+                offset,
+                size,
+                bitfield: Some((bit_offset, width)),
+            });
+            continue;
+        }
+        unit = None;
+
+        let offset = if is_union { 0 } else { align_to(layout.size, align) };
+        layout.size = if is_union {
+            layout.size.max(size)
+        } else {
+            offset + size
+        };
+        layout.members.push(MemberLayout {
+            name,
+            offset,
+            size,
+            bitfield: None,
+        });
+    }
+    layout.size = align_to(layout.size, layout.align);
+    layout
+}
+
+fn layout_json(layout: &Layout) -> String {
+    let members = layout
+        .members
+        .iter()
+        .map(|member| {
+            let bitfield = member
+                .bitfield
+                .map(|(bit_offset, bits)| format!(", \"bit_offset\": {bit_offset}, \"bits\": {bits}"))
+                .unwrap_or_default();
+            format!(
+                "{{\"name\": \"{}\", \"offset\": {}, \"size\": {}{bitfield}}}",
+                member.name, member.offset, member.size
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "{{\"size\": {}, \"align\": {}, \"members\": [{members}]}}",
+        layout.size, layout.align
+    )
+}
+
+fn align_to(offset: u64, align: u64) -> u64 {
+    (offset + align - 1) / align * align
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Places members declared in C, looking up the types vk.xml defines in `types`.
+    fn layout(code: &[&str], is_union: bool, target: Target, types: &[(&str, (u64, u64))]) -> Layout {
+        let type_layout = |ty: &c_decl::CType| match ty {
+            c_decl::CType::Named { name, .. } => primitive_layout(name, target)
+                .or_else(|| types.iter().find(|(ty, _)| ty == name).map(|(_, layout)| *layout))
+                .unwrap(),
+            c_decl::CType::Pointer { .. } => (target.pointer_size(), target.pointer_size()),
+            ty => unimplemented!("{ty:?}"),
+        };
+        let members = code
+            .iter()
+            .map(|code| {
+                let decl = c_decl::parse_declaration(code).unwrap();
+                (decl.name.unwrap(), type_layout(&decl.ty), decl.bitfield)
+            })
+            .collect();
+        place_members(members, is_union)
+    }
+
+    fn offsets(layout: &Layout) -> Vec<u64> {
+        layout.members.iter().map(|member| member.offset).collect()
+    }
+
+    #[test]
+    fn extent_3d() {
+        let code = ["uint32_t width", "uint32_t height", "uint32_t depth"];
+        for target in [Target::Bits64, Target::Bits32] {
+            let layout = layout(&code, false, target, &[]);
+            assert_eq!((layout.size, layout.align), (12, 4));
+            assert_eq!(offsets(&layout), [0, 4, 8]);
+        }
+    }
+
+    #[test]
+    fn clear_value_union() {
+        let types = [("VkClearColorValue", (16, 4)), ("VkClearDepthStencilValue", (8, 4))];
+        let code = ["VkClearColorValue color", "VkClearDepthStencilValue depthStencil"];
+        let layout = layout(&code, true, Target::Bits64, &types);
+        assert_eq!((layout.size, layout.align), (16, 4));
+        assert_eq!(offsets(&layout), [0, 0]);
+    }
+
+    #[test]
+    fn acceleration_structure_instance_bitfields() {
+        let types = [("VkTransformMatrixKHR", (48, 4)), ("VkGeometryInstanceFlagsKHR", (4, 4))];
+        let code = [
+            "VkTransformMatrixKHR transform",
+            "uint32_t instanceCustomIndex:24",
+            "uint32_t mask:8",
+            "uint32_t instanceShaderBindingTableRecordOffset:24",
+            "VkGeometryInstanceFlagsKHR flags:8",
+            "uint64_t accelerationStructureReference",
+        ];
+        let layout = layout(&code, false, Target::Bits64, &types);
+        assert_eq!((layout.size, layout.align), (64, 8));
+        assert_eq!(offsets(&layout), [0, 48, 48, 52, 52, 56]);
+        let bitfields = layout.members.iter().map(|member| member.bitfield).collect::<Vec<_>>();
+        assert_eq!(
+            bitfields,
+            [None, Some((0, 24)), Some((24, 8)), Some((0, 24)), Some((24, 8)), None]
+        );
+    }
+
+    #[test]
+    fn pointer_member_depends_on_target() {
+        let types = [("VkStructureType", (4, 4))];
+        let code = ["VkStructureType sType", "struct VkBaseOutStructure* pNext"];
+        let layout_64 = layout(&code, false, Target::Bits64, &types);
+        assert_eq!((layout_64.size, layout_64.align), (16, 8));
+        assert_eq!(offsets(&layout_64), [0, 8]);
+        let layout_32 = layout(&code, false, Target::Bits32, &types);
+        assert_eq!((layout_32.size, layout_32.align), (8, 4));
+        assert_eq!(offsets(&layout_32), [0, 4]);
+    }
+}
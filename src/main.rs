@@ -1,10 +1,27 @@
-use heck::{ToShoutySnakeCase, ToSnakeCase};
+use heck::ToShoutySnakeCase;
 use std::{
-    collections::{hash_map::Entry, HashMap}, fs::File, io::{Read, Seek, Write}, path::Path
+    cell::RefCell, collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap}, fs::File, io::{Read, Seek, Write}, path::Path
 };
 
 use regex::Regex;
-use vk_parse::{Registry, TypeSpec};
+use vk_parse::Registry;
+
+mod ash_gen;
+mod ash_wrappers;
+mod backend;
+mod basetypes;
+mod bitfields;
+mod c_decl;
+mod code_links;
+mod constants;
+mod defines;
+mod flags;
+mod host_sync;
+mod includes;
+mod layout;
+mod member_docs;
+mod valid_usage;
+mod validity;
 
 fn get_last_change_date() -> u64 {
     use std::process::Command;
@@ -23,16 +40,16 @@ fn main() {
     let (registry, _errors) = vk_parse::parse_file(Path::new("./Vulkan-Docs/xml/vk.xml")).unwrap();
     println!("{:?}", _errors);
     assert!(_errors.is_empty());
-    let converter = Converter::new(registry);
-    let header_version = match &converter.types.get("VK_HEADER_VERSION").as_ref().unwrap().spec {
-        TypeSpec::Code(code) => code.code.split(' ').last().unwrap(),
-        _ => panic!(),
-    };
+    let vkxml_registry = vk_parse::parse_file_as_vkxml(Path::new("./Vulkan-Docs/xml/vk.xml")).unwrap();
+    let converter = Converter::new(registry, ash_gen::AshGenerator::new(&vkxml_registry));
+    let header_version = converter.define_number("VK_HEADER_VERSION").unwrap();
     let last_changed_timestamp = get_last_change_date();
+    let handle_tree = converter.handle_tree();
     let mut file = File::create("./dist/index.json").unwrap();
     file.write_fmt(format_args!("{{
         \"version\": \"1.3.{header_version}\",
-        \"last_changed\": {last_changed_timestamp}
+        \"last_changed\": {last_changed_timestamp},
+        \"handles\": {handle_tree}
     }}")).unwrap();
     drop(file);
 
@@ -87,6 +104,10 @@ fn main() {
     }
 
     converter.find_redirects();
+    converter.write_host_sync_listing();
+    converter.write_unresolved_includes();
+    converter.write_vuid_index();
+    converter.write_platform_types();
 
     // Creating redirects
 }
@@ -99,6 +120,62 @@ struct Converter {
     consts: HashMap<String, vk_parse::Enum>,
     extensions: HashMap<String, vk_parse::Extension>,
     parents: HashMap<String, String>, // mapping from item to [feature, extension]
+    result_codes: BTreeMap<String, Vec<String>>, // mapping from VkResult code to commands returning it
+    ash: ash_gen::AshGenerator,
+    backends: Vec<Box<dyn backend::LanguageBackend>>,
+    code_links: RefCell<Vec<String>>, // links of the code blocks rendered for the current page
+    unresolved_includes: RefCell<BTreeMap<String, BTreeSet<String>>>, // mapping from include path to pages
+    valid_usage: BTreeMap<String, Vec<valid_usage::ValidUsage>>, // mapping from validusage.json scope to statements
+}
+
+/// Extensions whose commands are exported directly by the Vulkan loader.
+const LOADER_EXPORTED_EXTENSIONS: &[&str] = &[
+    "VK_KHR_surface",
+    "VK_KHR_swapchain",
+    "VK_KHR_display",
+    "VK_KHR_display_swapchain",
+    "VK_KHR_android_surface",
+    "VK_KHR_wayland_surface",
+    "VK_KHR_win32_surface",
+    "VK_KHR_xcb_surface",
+    "VK_KHR_xlib_surface",
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DispatchLevel {
+    Global,
+    Instance,
+    Device,
+}
+
+impl DispatchLevel {
+    fn as_str(self) -> &'static str {
+        match self {
+            DispatchLevel::Global => "global",
+            DispatchLevel::Instance => "instance",
+            DispatchLevel::Device => "device",
+        }
+    }
+}
+
+#[derive(Default)]
+struct HandleLifecycle {
+    created_by: Vec<String>,
+    allocated_by: Vec<String>,
+    destroyed_by: Vec<String>,
+    freed_by: Vec<String>,
+}
+
+fn handle_subtree(children: &BTreeMap<&str, Vec<&str>>, handles: &[&str]) -> String {
+    let entries = handles
+        .iter()
+        .map(|handle| {
+            let subtree = handle_subtree(children, children.get(handle).map(Vec::as_slice).unwrap_or(&[]));
+            format!("\"{handle}\": {subtree}")
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{{{entries}}}")
 }
 
 fn add_item_parent(parents: &mut HashMap<String, String>, item: &str, parent: &str) {
@@ -130,7 +207,7 @@ impl Converter {
             }
         }
     }
-    fn new(registry: Registry) -> Self {
+    fn new(registry: Registry, ash: ash_gen::AshGenerator) -> Self {
         let mut this = Self {
             registry,
             types: Default::default(),
@@ -139,6 +216,12 @@ impl Converter {
             consts: Default::default(),
             parents: Default::default(),
             extensions: Default::default(),
+            result_codes: Default::default(),
+            ash,
+            backends: backend::configured_backends(),
+            code_links: RefCell::new(Vec::new()),
+            unresolved_includes: Default::default(),
+            valid_usage: valid_usage::load(),
         };
         for child in this.registry.0.iter() {
             use vk_parse::RegistryChild;
@@ -301,6 +384,29 @@ impl Converter {
                 _ => (),
             }
         }
+        for command in this.commands.values() {
+            let vk_parse::Command::Definition(command) = command else {
+                continue;
+            };
+            // Commands no Vulkan feature or extension requires, such as the Vulkan SC ones, have no page.
+            if !this.parents.contains_key(&command.proto.name) {
+                continue;
+            }
+            for code in command
+                .successcodes
+                .iter()
+                .chain(command.errorcodes.iter())
+                .flat_map(|codes| codes.split(','))
+            {
+                this.result_codes
+                    .entry(code.trim().to_string())
+                    .or_default()
+                    .push(command.proto.name.clone());
+            }
+        }
+        for commands in this.result_codes.values_mut() {
+            commands.sort();
+        }
         this
     }
 
@@ -361,8 +467,18 @@ impl Converter {
                 additional_attributes += &self.parents[name];
                 additional_attributes += "\n";
             }
+            if name == "VkResult" {
+                additional_attributes += &self.result_code_attributes();
+            }
+            additional_attributes += &self.member_docs_attributes(name, file);
+            additional_attributes += &self.valid_usage_attributes(name);
         }
 
+        let page = Regex::new(r"\n(?:title|extension): (.+)\n")
+            .unwrap()
+            .captures(file)
+            .map(|capture| capture.get(1).unwrap().as_str().to_string())
+            .unwrap_or_default();
         let regex = Regex::new(r"\[\{generated\}(.*)\]\(\{generated\}(.*)\)").unwrap();
         let mut replacements = HashMap::new();
         for capture in regex.captures_iter(file) {
@@ -372,19 +488,32 @@ impl Converter {
             if path.starts_with("/api/structs/") {
                 let n = &path[13..path.len() - 5];
                 let generated_code = self.generate_api_struct(n);
+                additional_attributes += &self.struct_attributes(n);
+                additional_attributes += &self.bitfield_attributes(n);
+                additional_attributes += &self.layout_attributes(n);
+                additional_attributes += &self.struct_host_sync_attributes(n);
+                additional_attributes += &self.implicit_valid_usage_attributes(n);
                 replacements.insert(capture.get(0).unwrap().as_str().to_string(), generated_code);
             } else if path.starts_with("/api/flags/") {
                 let n = &path[11..path.len() - 5];
                 let generated_code = self.generate_flags(n);
+                additional_attributes += &self.flags_attributes(n);
                 replacements.insert(capture.get(0).unwrap().as_str().to_string(), generated_code);
             } else if path.starts_with("/api/protos/") {
                 let n = &path[12..path.len() - 5];
                 let generated_code = self.generate_fn_prototype(&n);
                 additional_attributes += &self.fn_attributes(&n);
+                additional_attributes += &self.implicit_valid_usage_attributes(n);
                 replacements.insert(capture.get(0).unwrap().as_str().to_string(), generated_code);
             } else if path.starts_with("/api/enums/") {
                 let n = &path[11..path.len() - 5];
                 let generated_code = self.generate_enum(n);
+                if self.consts.contains_key(n) {
+                    additional_attributes += &self.constant_attributes(n);
+                }
+                if let Some(flags) = self.flags_type_of(n) {
+                    additional_attributes += &format!("flags: {flags}\n");
+                }
                 replacements.insert(capture.get(0).unwrap().as_str().to_string(), generated_code);
             } else if path.starts_with("/api/basetypes/") {
                 let n = path
@@ -393,6 +522,7 @@ impl Converter {
                     .strip_suffix(".adoc")
                     .unwrap();
                 let generated_code = self.generate_basetype(&n);
+                additional_attributes += &self.basetype_attributes(n);
                 replacements.insert(capture.get(0).unwrap().as_str().to_string(), generated_code);
             } else if path.starts_with("/api/handles/") {
                 let n = path
@@ -401,6 +531,7 @@ impl Converter {
                     .strip_suffix(".adoc")
                     .unwrap();
                 let generated_code = self.generate_handles(n);
+                additional_attributes += &self.handle_attributes(n);
                 replacements.insert(capture.get(0).unwrap().as_str().to_string(), generated_code);
             } else if path.starts_with("/api/defines/") {
                 let n = path
@@ -409,6 +540,7 @@ impl Converter {
                     .strip_suffix(".adoc")
                     .unwrap();
                 let generated_code = self.generate_define(&n);
+                additional_attributes += &self.define_attributes(n);
                 replacements.insert(capture.get(0).unwrap().as_str().to_string(), generated_code);
             } else if path.starts_with("/api/funcpointers/") {
                 let n = path
@@ -419,12 +551,26 @@ impl Converter {
                 let generated_code = self.generate_fn_ptr(&n);
                 replacements.insert(capture.get(0).unwrap().as_str().to_string(), generated_code);
             } else if path.starts_with("/meta/{refprefix}"){
-                replacements.insert(capture.get(0).unwrap().as_str().to_string(), String::new());
+                let n = path
+                    .strip_prefix("/meta/{refprefix}")
+                    .unwrap()
+                    .strip_suffix(".adoc")
+                    .unwrap();
+                let generated_code = if self.extensions.contains_key(n) {
+                    self.render_tabs(|backend| backend.extension_blocks(self, n))
+                } else {
+                    String::new()
+                };
+                replacements.insert(capture.get(0).unwrap().as_str().to_string(), generated_code);
+            } else if let Some(generated_code) = self.generate_include(&path) {
+                replacements.insert(capture.get(0).unwrap().as_str().to_string(), generated_code);
             } else {
                 println!("Unknown path: {:?}", path);
+                self.record_unresolved_include(&path, &page);
                 continue;
             };
         }
+        additional_attributes += &self.code_links_attributes();
         let changed = !replacements.is_empty() || !additional_attributes.is_empty();
         for (key, replacement) in replacements.into_iter() {
             *file = file.replace(&key, &replacement);
@@ -436,294 +582,155 @@ impl Converter {
         changed
     }
     fn generate_fn_ptr(&self, name: &str) -> String {
-        let ty = &self.types[name];
-        // pub type PFN_vkDebugReportCallbackEXT =
-        //   Option<unsafe extern "system" fn(
-        //     flags: DebugReportFlagsEXT,
-        // object_type: DebugReportObjectTypeEXT, object: u64, location: usize, message_code: i32, p_layer_prefix: *const c_char, p_message: *const c_char, p_user_data: *mut c_void) -> Bool32>;
-
-        let (code, markup) = match &ty.spec {
-            vk_parse::TypeSpec::Code(code) => (code.code.as_str(), code.markup.as_slice()),
-            _ => unimplemented!(),
-        };
-        let return_type = Regex::new(r"typedef +(.+) +\(")
-            .unwrap()
-            .captures(code)
-            .unwrap()
-            .get(1)
-            .unwrap()
-            .as_str();
-        let return_type = convert_c_type_to_rust(return_type);
-        let members = code
-            .split("\n")
-            .skip(1)
-            .zip(markup.iter().skip(1))
-            .map(|(line, markup)| {
-                let variable_name = Regex::new(r".* (\w+)[,\);]*")
-                    .unwrap()
-                    .captures(line)
-                    .unwrap()
-                    .get(1)
-                    .unwrap()
-                    .as_str()
-                    .trim();
-                let type_name = Regex::new(r"(.*) +\w+[,\);]*")
-                    .unwrap()
-                    .captures(line)
-                    .unwrap()
-                    .get(1)
-                    .unwrap()
-                    .as_str()
-                    .trim();
-                (
-                    variable_name.to_snake_case(),
-                    convert_c_type_to_rust(type_name),
-                )
-            })
-            .fold(String::new(), |a, (variable_name, type_name)| {
-                a + "        " + &variable_name + ": " + &type_name + ",\n"
-            })
-            .trim_end()
-            .to_string();
-
-        format!(
-            "::code-group
-```c [C]
-{code}
-```
-```rs [Rust]
-pub type {name} = Option<
-    unsafe extern \"system\" fn(
-{members}
-    ) -> {return_type}
->;
-```
-::"
-        )
+        self.render_tabs(|backend| backend.funcpointer_blocks(self, name))
     }
-    fn generate_define(&self, name: &str) -> String {
+    fn handle_attributes(&self, name: &str) -> String {
         let ty = &self.types[name];
-        let code = match &ty.spec {
-            vk_parse::TypeSpec::Code(code) => code.code.as_str(),
-            _ => unimplemented!(),
-        };
-        return format!(
-            "```c
-{code}
-```
-"
-        );
+        if ty.alias.is_some() {
+            return String::new();
+        }
+        let mut attributes = String::new();
+        if let Some(parent) = &ty.parent {
+            attributes += &format!("handle_parent: [{parent}]\n");
+        }
+        if let Some(objtypeenum) = &ty.objtypeenum {
+            attributes += &format!("object_type: {objtypeenum}\n");
+        }
+        attributes += &format!("dispatchable: {}\n", self.is_dispatchable_handle(name));
+        let lifecycle = self.handle_lifecycle(name);
+        for (key, commands) in [
+            ("created_by", &lifecycle.created_by),
+            ("allocated_by", &lifecycle.allocated_by),
+            ("destroyed_by", &lifecycle.destroyed_by),
+            ("freed_by", &lifecycle.freed_by),
+        ] {
+            if !commands.is_empty() {
+                attributes += &format!("{key}: [{}]\n", commands.join(", "));
+            }
+        }
+        attributes
     }
-    fn generate_handles(&self, name: &str) -> String {
-        let ty = &self.types[name];
-        if let Some(alias) = &ty.alias {
-            let rs_name = name.strip_prefix("Vk").unwrap();
-            let rs_alias = alias.strip_prefix("Vk").unwrap();
-            return format!(
-                "::code-group
-```c [C]
-typedef {alias} {name};
-```
-```rs [Rust]
 
-```
-type {rs_name} = vk::{rs_alias};
-"
-            );
+    /// Finds the commands creating, allocating, destroying or freeing a handle from their signatures.
+    /// Creation commands return the handle through their last non-const handle pointer, while
+    /// destruction commands take it as their last handle parameter.
+    fn handle_lifecycle(&self, handle: &str) -> HandleLifecycle {
+        let mut lifecycle = HandleLifecycle::default();
+        for command in self.commands.values() {
+            let vk_parse::Command::Definition(command) = command else {
+                continue;
+            };
+            let name = command.proto.name.as_str();
+            let (commands, creates) = if name.starts_with("vkCreate") {
+                (&mut lifecycle.created_by, true)
+            } else if name.starts_with("vkAllocate") {
+                (&mut lifecycle.allocated_by, true)
+            } else if name.starts_with("vkDestroy") {
+                (&mut lifecycle.destroyed_by, false)
+            } else if name.starts_with("vkFree") {
+                (&mut lifecycle.freed_by, false)
+            } else {
+                continue;
+            };
+            let target = command
+                .params
+                .iter()
+                .filter(|param| param.api.as_ref().map(String::as_str) != Some("vulkansc"))
+                .filter(|param| {
+                    let Some(type_name) = &param.definition.type_name else {
+                        return false;
+                    };
+                    let is_handle = self.types.get(type_name).and_then(|ty| ty.category.as_deref())
+                        == Some("handle");
+                    let is_output = param.definition.code.contains('*')
+                        && !param.definition.code.trim_start().starts_with("const");
+                    is_handle && (!creates || is_output)
+                })
+                .last()
+                .and_then(|param| param.definition.type_name.as_ref());
+            if target.map(|target| self.resolve_type_alias(target)) == Some(handle) {
+                commands.push(name.to_string());
+            }
         }
-        let code = match &ty.spec {
-            vk_parse::TypeSpec::Code(code) => code.code.as_str(),
-            _ =>  {
-                println!("{name}");
-                unimplemented!()
-            },
-        };
-        let rs_name = name.strip_prefix("Vk").unwrap();
-        return format!(
-            "::code-group
-```c [C]
-{code}
-```
-```rs [Rust]
-#[repr(transparent)]
-pub struct {rs_name}(_);
-```
-::
-"
-        );
+        lifecycle.created_by.sort();
+        lifecycle.allocated_by.sort();
+        lifecycle.destroyed_by.sort();
+        lifecycle.freed_by.sort();
+        lifecycle
     }
-    fn generate_basetype(&self, name: &str) -> String {
-        let ty = &self.types[name];
-        let code = match &ty.spec {
-            vk_parse::TypeSpec::Code(code) => code.code.as_str(),
-            _ => unimplemented!(),
-        };
-        return format!(
-            "```c
-{code}
-```"
-        );
+
+    /// All handles nested under their parent handle, as a JSON object.
+    fn handle_tree(&self) -> String {
+        let mut children: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        let mut roots = Vec::new();
+        for (name, ty) in self.types.iter() {
+            if ty.category.as_ref().map(String::as_str) != Some("handle") || ty.alias.is_some() {
+                continue;
+            }
+            match ty.parent.as_ref().and_then(|parent| parent.split(',').next()) {
+                Some(parent) => children.entry(parent.trim()).or_default().push(name),
+                None => roots.push(name.as_str()),
+            }
+        }
+        for handles in children.values_mut() {
+            handles.sort();
+        }
+        roots.sort();
+        handle_subtree(&children, &roots)
+    }
+
+    fn generate_handles(&self, name: &str) -> String {
+        self.render_tabs(|backend| backend.handle_blocks(self, name))
     }
     fn generate_enum(&self, name: &str) -> String {
         // three cases here: enum def, enum alias, const value
-        if !self.enums.contains_key(name) {
-            if !self.consts.contains_key(name) {
-                // enum alias
-                let ty = &self.types[name];
-                let alias = ty.alias.as_ref().unwrap();
-                let rs_name = name.strip_prefix("Vk").unwrap();
-                let rs_alias = alias.strip_prefix("Vk").unwrap();
-                return format!(
-                    "::code-group
-```c [C]
-#define {name} {alias}
-```
-```rs [Rust]
-const {rs_name}: _ = vk::{rs_alias};
-```
-::"
-                );
-            }
-            let rs_name = &name[3..];
-            let val = &self.consts[name];
-            match &val.spec {
-                vk_parse::EnumSpec::None => todo!(),
-                vk_parse::EnumSpec::Value { value, extends } => {
-                    assert!(extends.is_none());
-                    let (rs_type, rs_value) = convert_c_enum_init_value_to_rust(value);
-                    return format!(
-                        "::code-group
-```c [C]
-#define {name} {value}
-```
-```rs [Rust]
-const {rs_name}: {rs_type} = {rs_value};
-```
-::"
-                    );
-                }
-                vk_parse::EnumSpec::Alias { alias, extends } => {
-                    let rs_alias_name = &alias[3..];
-                    let target = &self.consts[alias];
-                    let vk_parse::EnumSpec::Value { value, extends } = &target.spec else {
-                        panic!()
-                    };
-                    let (rs_type, rs_value) = convert_c_enum_init_value_to_rust(value);
-                    return format!(
-                        "::code-group
-```c [C]
-#define {name} {alias}
-```
-```rs [Rust]
-const {rs_name}: {rs_type} = vk::{rs_alias_name};
-```
-::"
-                    );
-                },
-                _ => unimplemented!()
-            }
+        if self.consts.contains_key(name) {
+            return self.render_tabs(|backend| backend.constant_blocks(self, name));
         }
+        let result = self.render_tabs(|backend| backend.enum_blocks(self, name));
+        if name == "VkResult" {
+            return result + &self.generate_result_code_table();
+        }
+        result
+    }
 
-        // enum definition
+    /// Resolves constant aliases and evaluates the value with the `type` declared in vk.xml.
+    fn evaluate_constant(&self, name: &str) -> (&vk_parse::Enum, constants::ConstValue) {
+        let name = constants::resolve_alias(name, |name| match &self.consts[name].spec {
+            vk_parse::EnumSpec::Alias { alias, .. } => Some(alias.as_str()),
+            _ => None,
+        });
+        let constant = &self.consts[name];
+        let vk_parse::EnumSpec::Value { value, .. } = &constant.spec else {
+            unimplemented!()
+        };
+        let c_type = constant.type_suffix.as_deref().unwrap();
+        (constant, constants::evaluate(c_type, value).unwrap())
+    }
 
-        let e = &self.enums[name];
-        let rs_name = &name[2..];
-        let children = e
-            .children
-            .iter()
-            .map(|a| match a {
-                vk_parse::EnumsChild::Enum(d) => match &d.spec {
-                    vk_parse::EnumSpec::None => d.name.clone(),
-                    vk_parse::EnumSpec::Alias { alias, extends } => {
-                        assert!(extends.is_none());
-                        format!("{} = {}", d.name, alias)
-                    }
-                    vk_parse::EnumSpec::Bitpos { bitpos, extends } => {
-                        assert!(extends.is_none());
-                        let value: u64 = 1 << bitpos;
-                        if let Some(bitwidth) = e.bitwidth {
-                            assert_eq!(bitwidth, 64);
-                            format!("{} = {:#010x}ULL", d.name, value)
-                        } else {
-                            format!("{} = {:#010x}", d.name, value)
-                        }
-                    }
-                    vk_parse::EnumSpec::Value { value, extends } => {
-                        assert!(extends.is_none());
-                        format!("{} = {}", d.name, value)
-                    }
-                    _ => todo!(),
-                },
-                vk_parse::EnumsChild::Unused(_) => String::new(),
-                vk_parse::EnumsChild::Comment(comment) => {
-                    format!("// {comment}")
-                }
-                _ => todo!(),
-            })
-            .fold(String::new(), |a, b| a + "    " + &b + ",\n")
-            .trim_end()
-            .to_string();
-        let children_rs = e
-            .children
-            .iter()
-            .filter_map(|a| match a {
-                vk_parse::EnumsChild::Enum(d) => {
-                    if d.deprecated.is_some() {
-                        return None;
-                    }
-                    match &d.spec {
-                        vk_parse::EnumSpec::None => Some(variant_ident(name, &d.name)),
-                        vk_parse::EnumSpec::Alias { alias, extends } => {
-                            let alias = variant_ident(name, &alias);
-                            let dname = variant_ident(name, &d.name);
-                            assert!(extends.is_none());
-                            Some(format!("pub const {}: Self = Self::{};", dname, alias))
-                        }
-                        vk_parse::EnumSpec::Bitpos { bitpos, extends } => {
-                            assert!(extends.is_none());
-                            let value: u64 = 1 << bitpos;
-                            let dname = variant_ident(name, &d.name);
-                            Some(format!("pub const {}: Self = {:#010x};", dname, value))
-                        }
-                        vk_parse::EnumSpec::Value { value, extends } => {
-                            assert!(extends.is_none());
-                            let dname = variant_ident(name, &d.name);
-                            Some(format!("pub const {}: Self = {};", dname, value))
-                        }
-                        _ => todo!(),
-                    }
-                }
-                vk_parse::EnumsChild::Unused(_) => None,
-                vk_parse::EnumsChild::Comment(comment) => Some(format!("// {comment}")),
-                _ => todo!(),
-            })
-            .fold(String::new(), |a, b| a + "    " + &b + "\n")
-            .trim_end()
-            .to_string();
-        let rust_type = if e.bitwidth.is_some() { "u64" } else { "u32" };
-        let result = format!(
-            "::code-group
-```c [C]
-typedef enum {name} {{
-{children}
-}} {name};
-```
-```rs [Rust]
-pub struct {rs_name}({rust_type});
-impl {rs_name} {{
-{children_rs}
-}}
-```
-::"
+    fn constant_attributes(&self, name: &str) -> String {
+        let (constant, value) = self.evaluate_constant(name);
+        let mut attributes = format!(
+            "c_type: {}\nvalue: \"{}\"\n",
+            constant.type_suffix.as_deref().unwrap(),
+            value.decimal()
         );
-        result
+        if let Some(hex) = value.hex() {
+            attributes += &format!("value_hex: \"{hex}\"\n");
+        }
+        attributes
     }
 
     fn fn_attributes(&self, name: &str) -> String {
         let mut attributes = String::new();
+        let dispatch_level = self.dispatch_level(self.command_definition(name));
+        attributes += &format!("dispatch_level: {}\n", dispatch_level.as_str());
+        attributes += &format!("pfn: PFN_{name}\n");
+        attributes += &format!("loader_export: {}\n", self.is_loader_exported(name));
+        attributes += &self.ash_attributes(name);
         let command = &self.commands[name];
         let vk_parse::Command::Definition(command) = command else {
-            return String::new();
+            return attributes;
         };
         if let Some(cmdbufferlevel) = &command.cmdbufferlevel {
             attributes += &format!("cmd_buf_level: [{cmdbufferlevel}]\n");
@@ -740,311 +747,202 @@ impl {rs_name} {{
         if let Some(tasks) = &command.tasks {
             attributes += &format!("tasks: [{tasks}]\n");
         }
+        if let Some(success_codes) = &command.successcodes {
+            attributes += &format!("success_codes: [{success_codes}]\n");
+        }
+        if let Some(error_codes) = &command.errorcodes {
+            attributes += &format!("error_codes: [{error_codes}]\n");
+        }
+        attributes += &self.command_host_sync_attributes(command);
+        attributes
+    }
+
+    /// Reverse index from each `VkResult` code to the commands that can return it.
+    fn result_code_attributes(&self) -> String {
+        let mut attributes = "returned_by:\n".to_string();
+        for (code, commands) in self.result_codes.iter() {
+            attributes += &format!("  {code}: [{}]\n", commands.join(", "));
+        }
         attributes
     }
+
+    fn generate_result_code_table(&self) -> String {
+        let rows = self
+            .result_codes
+            .iter()
+            .map(|(code, commands)| {
+                let commands = commands
+                    .iter()
+                    .map(|command| format!("[{command}](/man/{command})"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("| :anchor{{id=\"{code}\"}} `{code}` | {commands} |")
+            })
+            .fold(String::new(), |a, b| a + &b + "\n");
+        format!(
+            "
+
+| Code | Returned by |
+| ---- | ----------- |
+{rows}"
+        )
+    }
     fn generate_fn_prototype(&self, name: &str) -> String {
+        self.render_tabs(|backend| backend.command_blocks(self, name))
+    }
+
+    /// Follows command aliases until reaching the command definition.
+    fn command_definition(&self, name: &str) -> &vk_parse::CommandDefinition {
         let mut command = &self.commands[name];
         loop {
             match command {
-                vk_parse::Command::Alias { name, alias } => command = &self.commands[alias],
-                vk_parse::Command::Definition(_) => break,
+                vk_parse::Command::Alias { alias, .. } => command = &self.commands[alias],
+                vk_parse::Command::Definition(definition) => return definition,
                 _ => todo!(),
             }
         }
-        let vk_parse::Command::Definition(mut command) = command.clone() else {
-            unreachable!()
-        };
-        command.proto.name = name.to_string();
-        let return_type = command
-            .proto
-            .type_name
-            .as_ref()
-            .map(|s| s.as_str())
-            .unwrap_or("");
-        let fn_name = command.proto.name.as_str();
-        let rs_fn_name = fn_name.strip_prefix("vk").unwrap().to_snake_case();
-        let rs_ret_ty = if return_type == "void" {
-            String::new()
-        } else {
-            " -> ".to_string() + convert_c_type_to_rust(return_type).as_str()
-        };
-        let params = command
-            .params
-            .iter()
-            .filter(|a| a.api.as_ref().map(String::as_str) != Some("vulkansc"))
-            .map(|a| &a.definition.code)
-            .fold(String::new(), |a, b| a + "    " + &b + ",\n");
+    }
 
-        let rs_params = command
-            .params
-            .iter()
-            .map(|a| {
-                use generator::FieldExt;
-                let raw_ty = a.definition.type_name.as_ref().unwrap();
-                let rs_type = a.type_tokens(true, None).to_string();
-                let mut rs_type = rs_type
-                    .replace("* const", "*const")
-                    .replace("* mut", "*mut");
-                let rs_name = a.param_ident();
+    /// Follows type aliases until reaching the type definition.
+    fn resolve_type_alias<'a>(&'a self, mut name: &'a str) -> &'a str {
+        while let Some(alias) = self.types.get(name).and_then(|ty| ty.alias.as_ref()) {
+            name = alias;
+        }
+        name
+    }
 
-                if raw_ty.starts_with("Vk") {
-                    let stripped = raw_ty.strip_prefix("Vk").unwrap();
-                    rs_type = rs_type.replace(&stripped, &("vk::".to_string() + stripped));
-                }
+    fn is_dispatchable_handle(&self, name: &str) -> bool {
+        let Some(ty) = self.types.get(self.resolve_type_alias(name)) else {
+            return false;
+        };
+        if ty.category.as_ref().map(String::as_str) != Some("handle") {
+            return false;
+        }
+        match &ty.spec {
+            vk_parse::TypeSpec::Code(code) => code
+                .markup
+                .iter()
+                .any(|a| matches!(a, vk_parse::TypeCodeMarkup::Type(ty) if ty == "VK_DEFINE_HANDLE")),
+            _ => false,
+        }
+    }
 
-                format!("{rs_name}: {rs_type}")
-            })
-            .fold(String::new(), |a, b| a + "    " + &b + ",\n");
-        let params = params[..params.len() - 2].to_string();
-        format!(
-            "::code-group
-```c [C]
-{return_type} {fn_name}(
-{params});
-```
-```rs [Rust]
-pub fn {rs_fn_name}(
-{rs_params}){rs_ret_ty};
-```
-::"
-        )
+    /// The handle itself followed by its chain of parent handles, up to the root.
+    fn handle_ancestry<'a>(&'a self, name: &'a str) -> Vec<&'a str> {
+        let mut ancestry = vec![self.resolve_type_alias(name)];
+        while let Some(parent) = self
+            .types
+            .get(*ancestry.last().unwrap())
+            .and_then(|ty| ty.parent.as_ref())
+            .and_then(|parent| parent.split(',').next())
+        {
+            let parent = self.resolve_type_alias(parent.trim());
+            if ancestry.contains(&parent) {
+                break;
+            }
+            ancestry.push(parent);
+        }
+        ancestry
     }
-    fn generate_flags(&self, name: &str) -> String {
-        let ty = &self.types[name];
-        if let Some(alias) = &ty.alias {
-            let rs_name = name.strip_prefix("Vk").unwrap();
-            let rs_alias = alias.strip_prefix("Vk").unwrap();
-            return format!(
-                "::code-group
-```c [C]
-typedef {alias} {name};
-```
-```rs [Rust]
-pub type {rs_name} = vk::{rs_alias}; 
-```
-::"
-            )
+
+    fn dispatch_level(&self, command: &vk_parse::CommandDefinition) -> DispatchLevel {
+        // Both are queried through vkGetInstanceProcAddr regardless of their first parameter.
+        match command.proto.name.as_str() {
+            "vkGetInstanceProcAddr" => return DispatchLevel::Global,
+            "vkGetDeviceProcAddr" => return DispatchLevel::Instance,
+            _ => (),
         }
-        match &ty.spec {
-            vk_parse::TypeSpec::Code(code) => {
-                let c_code = code.code.as_str();
-                assert_eq!(code.markup.len(), 2);
-                let ty = code
+        let Some(first_param) = command
+            .params
+            .first()
+            .and_then(|param| param.definition.type_name.as_ref())
+        else {
+            return DispatchLevel::Global;
+        };
+        if !self.is_dispatchable_handle(first_param) {
+            return DispatchLevel::Global;
+        }
+        if self.handle_ancestry(first_param).contains(&"VkDevice") {
+            DispatchLevel::Device
+        } else {
+            DispatchLevel::Instance
+        }
+    }
+
+    /// Whether the Vulkan loader exports the command as a static symbol, which is
+    /// the case for core commands and the WSI extensions it implements itself.
+    fn is_loader_exported(&self, name: &str) -> bool {
+        let Some(parents) = self.parents.get(name) else {
+            return false;
+        };
+        parents.split(", ").any(|parent| {
+            parent.starts_with("VK_VERSION_") || LOADER_EXPORTED_EXTENSIONS.contains(&parent)
+        })
+    }
+    fn struct_attributes(&self, name: &str) -> String {
+        let mut attributes = String::new();
+        let Some(vk_parse::TypeSpec::Members(members)) = self.types.get(name).map(|ty| &ty.spec) else {
+            return attributes;
+        };
+        // The `values` attribute of `sType` is what ash's `TaggedStructure::STRUCTURE_TYPE` uses.
+        let structure_type = members.iter().find_map(|member| match member {
+            vk_parse::TypeMember::Definition(def) => {
+                let is_s_type = def
                     .markup
                     .iter()
-                    .filter_map(|a| match a {
-                        vk_parse::TypeCodeMarkup::Type(ty) => Some(ty),
-                        _ => None,
-                    })
-                    .next()
-                    .unwrap();
-                let ty = if ty.as_str() == "VkFlags" {
-                    "u32"
-                } else if ty.as_str() == "VkFlags64" {
-                    "u64"
+                    .any(|markup| matches!(markup, vk_parse::TypeMemberMarkup::Name(name) if name == "sType"));
+                if is_s_type {
+                    def.values.as_ref()
                 } else {
-                    unimplemented!()
-                };
-                let name = code
-                    .markup
-                    .iter()
-                    .filter_map(|a| match a {
-                        vk_parse::TypeCodeMarkup::Name(name) => Some(name),
-                        _ => None,
-                    })
-                    .next()
-                    .unwrap();
-                let name = &name[2..name.len()];
-                format!(
-                    "::code-group
-```c [C]
-{c_code}
-```
-```rs [Rust]
-pub struct {name}({ty}); 
-```
-::"
-                )
+                    None
+                }
             }
-            _ => unimplemented!(),
+            _ => None,
+        });
+        if let Some(structure_type) = structure_type {
+            attributes += &format!("structure_type: {structure_type}\n");
+        }
+        if self.ash.has_lifetime(name) {
+            attributes += "rust_lifetime: true\n";
         }
+        attributes
     }
 
     fn generate_api_struct(&self, name: &str) -> String {
-        let ty = &self.types[name];
-        let category = ty.category.as_ref().map(String::as_str).unwrap_or("struct");
-        if let Some(alias) = &ty.alias {
-            let rs_name = name.strip_prefix("Vk").unwrap();
-            let rs_alias = alias.strip_prefix("Vk").unwrap();
-            return format!(
-                "::code-group
-```c [C]
-typedef {alias} {name};
-```
-```rs [Rust]
-type {rs_name} = vk::{rs_alias};
-```
-::"
-            );
-        }
-        match &ty.spec {
-            vk_parse::TypeSpec::None => {
-                unimplemented!()
-            }
-            vk_parse::TypeSpec::Code(_) => todo!(),
-            vk_parse::TypeSpec::Members(members) => {
-                let c_members = members
-                    .iter()
-                    .filter_map(|member| {
-                        let result = match member {
-                            vk_parse::TypeMember::Comment(comment) => format!("// {comment}"),
-                            vk_parse::TypeMember::Definition(def) => {
-                                if let Some(api) = &def.api {
-                                    if api != "vulkan" {
-                                        return None;
-                                    }
-                                }
-                                Regex::new(r" +")
-                                .unwrap()
-                                .replace_all(&def.code, " ")
-                                .to_string()
-                            },
-                            _ => todo!(),
-                        };
-                        Some(result)
-                    })
-                    .fold(String::new(), |a, b| a + "    " + &b + ";\n")
-                    .trim_end()
-                    .to_string();
-                let rs_members = members
-                    .iter()
-                    .filter_map(|member| match member {
-                        vk_parse::TypeMember::Comment(comment) => Some(format!("/// {comment}")),
-                        vk_parse::TypeMember::Definition(def) => {
-                            use generator::FieldExt;
-
-                            if let Some(api) = &def.api {
-                                if api != "vulkan" {
-                                    return None;
-                                }
-                            }
-
-                            let element: vkxml::StructElement = member.clone().into();
-                            let field = match element {
-                                vkxml::StructElement::Member(field) => field,
-                                _ => unreachable!(),
-                            };
-
-                            let raw_ty = def
-                                .markup
-                                .iter()
-                                .filter_map(|markup| match markup {
-                                    vk_parse::TypeMemberMarkup::Type(a) => Some(a.as_str()),
-                                    _ => None,
-                                })
-                                .next()
-                                .unwrap_or("unknown");
-                            let name = def
-                                .markup
-                                .iter()
-                                .filter_map(|markup| match markup {
-                                    vk_parse::TypeMemberMarkup::Name(a) => Some(a.as_str()),
-                                    _ => None,
-                                })
-                                .next()
-                                .unwrap_or("unknown");
-                            let name = name.to_snake_case();
-                            let ty = field.type_tokens(true, None).to_string();
-                            let mut ty = ty.replace("* const", "*const").replace("* mut", "*mut");
-                            if raw_ty.starts_with("Vk") {
-                                let stripped = raw_ty.strip_prefix("Vk").unwrap();
-                                ty = ty.replace(&stripped, &("vk::".to_string() + stripped));
-                            }
-                            let comment = def
-                                .markup
-                                .iter()
-                                .filter_map(|markup| match markup {
-                                    vk_parse::TypeMemberMarkup::Comment(a) => Some(a.as_str()),
-                                    _ => None,
-                                })
-                                .next()
-                                .unwrap_or("");
-                            if comment.is_empty() {
-                                Some(format!("{name}: {ty},"))
-                            } else {
-                                Some(format!("{name}: {ty}, // {comment}"))
-                            }
-                        }
-                        _ => todo!(),
-                    })
-                    .fold(String::new(), |a, b| a + "    " + &b + "\n")
-                    .trim_end()
-                    .to_string();
-                let rs_name = name.strip_prefix("Vk").unwrap();
-                format!(
-                    "::code-group
-```c [C]
-typedef {category} {name} {{
-{c_members}
-}} {name};
-```
-```rs [Rust]
-pub {category} {rs_name} {{
-{rs_members}
-}}
-```
-::"
-                )
-            }
-            _ => todo!(),
-        }
+        self.render_tabs(|backend| backend.struct_blocks(self, name))
     }
 }
 
-fn convert_c_type_to_rust(c_type: &str) -> String {
-    if c_type.starts_with("Vk") {
-        return "vk::".to_string() + &c_type[2..];
-    }
-    match c_type {
-        "void" => "std::ffi::c_void".to_string(),
-        "void*" => "*mut std::ffi::c_void".to_string(),
-        "const void*" => "*const std::ffi::c_void".to_string(),
-        "uint64_t" => "u64".to_string(),
-        "uint32_t" => "u32".to_string(),
-        "uint16_t" => "u16".to_string(),
-        "uint8_t" => "u8".to_string(),
-        "int64_t" => "i64".to_string(),
-        "int32_t" => "i32".to_string(),
-        "int16_t" => "i16".to_string(),
-        "int8_t" => "i8".to_string(),
-        "int" => "i32".to_string(),
-        "float" => "f32".to_string(),
-        "size_t" => "usize".to_string(),
-        "char" => "std::ffi::c_char".to_string(),
-        "const char*" => "*const std::ffi::c_char".to_string(),
-        "char*" => "*mut std::ffi::c_char".to_string(),
-        _ => c_type.to_string(),
+/// Quotes a string as JSON, which doubles as a YAML double-quoted scalar in frontmatter.
+fn json_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
     }
+    quoted.push('"');
+    quoted
 }
 
-fn convert_c_enum_init_value_to_rust(c_value: &str) -> (&'static str, String) {
-    let inferred_rust_type = if c_value.contains("ULL") {
-        "u64"
-    } else if c_value.contains("U") {
-        "u32"
-    } else if c_value.contains("F") {
-        "f32"
-    } else {
-        "usize"
+/// The type ash declares an API constant with. Plain integer constants are array sizes and
+/// therefore `usize`, while `VK_TRUE` and `VK_FALSE` are `Bool32`.
+fn ash_constant_type(constant: &vk_parse::Enum) -> &'static str {
+    let vk_parse::EnumSpec::Value { value, .. } = &constant.spec else {
+        unreachable!()
     };
-    let rust_value = c_value
-        .replace("~", "!")
-        .replace("(", "")
-        .replace(")", "")
-        .replace("ULL", "u64")
-        .replace("U", "u32")
-        .replace("F", "f32");
-    (inferred_rust_type, rust_value)
+    let c_type = constant.type_suffix.as_deref().unwrap();
+    match constant.name.as_str() {
+        "VK_TRUE" | "VK_FALSE" => "vk::Bool32",
+        _ if c_type == "uint32_t" && value.chars().all(|c| c.is_ascii_digit()) => "usize",
+        _ => constants::evaluate(c_type, value).unwrap().rust_type(),
+    }
 }
 
 pub fn variant_ident(enum_name: &str, variant_name: &str) -> String {
@@ -0,0 +1,121 @@
+//! Descriptions of struct members and command parameters, taken from the Members and Parameters
+//! sections of a refpage so that the site can show them when hovering fields in the code tabs.
+
+use std::collections::BTreeMap;
+
+use regex::Regex;
+
+use crate::{c_decl, json_string, Converter};
+
+impl Converter {
+    /// `member_docs` maps each member or parameter to the Markdown of the list item describing
+    /// it. Items describing several members at once, such as "`x` and `y` are ...", are attached
+    /// to each of them.
+    pub(crate) fn member_docs_attributes(&self, name: &str, markdown: &str) -> String {
+        let (section, names) = match self.described_names(name) {
+            Some(described) => described,
+            None => return String::new(),
+        };
+        let code = Regex::new(r"`(\w+)`").unwrap();
+        let mut docs = BTreeMap::new();
+        for item in section_items(markdown, section) {
+            let subject_end = [" is ", " are "]
+                .iter()
+                .filter_map(|verb| item.find(verb))
+                .min()
+                .unwrap_or(item.len());
+            for capture in code.captures_iter(&item[..subject_end]) {
+                let member = capture.get(1).unwrap().as_str();
+                if names.iter().any(|name| name == member) {
+                    docs.entry(member.to_string()).or_insert_with(|| item.clone());
+                }
+            }
+        }
+        if docs.is_empty() {
+            return String::new();
+        }
+        let docs = docs
+            .iter()
+            .map(|(member, description)| format!("{}: {}", json_string(member), json_string(description)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("member_docs: {{{docs}}}\n")
+    }
+
+    /// The refpage section describing the members or parameters of a type or command, and their
+    /// names as declared in vk.xml.
+    fn described_names(&self, name: &str) -> Option<(&'static str, Vec<String>)> {
+        if self.commands.contains_key(name) {
+            let names = self
+                .command_definition(name)
+                .params
+                .iter()
+                .filter(|param| param.api.as_ref().map(String::as_str) != Some("vulkansc"))
+                .map(|param| param.definition.name.clone())
+                .collect();
+            return Some(("Parameters", names));
+        }
+        let ty = self.types.get(name)?;
+        if ty.alias.is_some() {
+            return None;
+        }
+        match (&ty.spec, ty.category.as_deref()) {
+            (vk_parse::TypeSpec::Members(members), _) => {
+                let names = members
+                    .iter()
+                    .filter_map(|member| match member {
+                        vk_parse::TypeMember::Definition(def)
+                            if def.api.as_deref().map_or(true, |api| api == "vulkan") =>
+                        {
+                            c_decl::parse_declaration(&def.code).ok()?.name
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                Some(("Members", names))
+            }
+            (vk_parse::TypeSpec::Code(code), Some("funcpointer")) => {
+                let decl = c_decl::parse_declaration(&code.code).ok()?;
+                let c_decl::CType::Pointer { pointee, .. } = decl.ty else {
+                    return None;
+                };
+                let c_decl::CType::Function { params, .. } = *pointee else {
+                    return None;
+                };
+                Some(("Parameters", params.into_iter().filter_map(|param| param.name).collect()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The text of the top level list items under the heading titled `section`, with continuation
+/// lines joined.
+fn section_items(markdown: &str, section: &str) -> Vec<String> {
+    let mut lines = markdown.lines().skip_while(|line| {
+        !(line.starts_with('#') && line.trim_start_matches('#').trim() == section)
+    });
+    if lines.next().is_none() {
+        return Vec::new();
+    }
+    let mut items: Vec<String> = Vec::new();
+    let mut in_item = false;
+    for line in lines {
+        if line.starts_with('#') {
+            break;
+        }
+        if let Some(item) = line.strip_prefix("* ").or_else(|| line.strip_prefix("- ")) {
+            items.push(item.trim().to_string());
+            in_item = true;
+        } else if line.trim().is_empty() {
+            continue;
+        } else if in_item && line.starts_with(char::is_whitespace) {
+            let item = items.last_mut().unwrap();
+            item.push(' ');
+            item.push_str(line.trim());
+        } else {
+            in_item = false;
+        }
+    }
+    items
+}
@@ -69,7 +69,7 @@ async function main() {
                 type: yaml.type,
                 url: '/man/' + yaml.title,
             };
-            for ( const key of ['cmd_buf_level', 'render_pass_scope', 'supported_queue_types', 'tasks', 'video_coding_scope']) {
+            for ( const key of ['cmd_buf_level', 'render_pass_scope', 'supported_queue_types', 'tasks', 'video_coding_scope', 'success_codes', 'error_codes', 'dispatch_level', 'pfn', 'loader_export', 'ash']) {
                 if (yaml[key]) {
                     doc.command = doc.command || {};
                     doc.command[key] = yaml[key];
@@ -0,0 +1,222 @@
+//! Valid usage statements from `validusage.json`, which Vulkan-Docs builds with the `validusage`
+//! makeSpec target. Explicit statements are attached to their pages, and every VUID is indexed so
+//! that validation layer messages can be resolved to a page.
+
+use std::{collections::BTreeMap, fs::File, io::Write};
+
+use regex::Regex;
+
+use crate::{json_string, Converter};
+
+const VALIDUSAGE_PATH: &str = "./Vulkan-Docs/gen/out/validation/validusage.json";
+
+pub(crate) struct ValidUsage {
+    vuid: String,
+    /// The statement as HTML.
+    text: String,
+    /// The versions and extensions expression the statement depends on, such as
+    /// `(VK_VERSION_1_1,VK_KHR_device_group)`, or `None` for core statements.
+    condition: Option<String>,
+}
+
+/// The statements of `validusage.json` keyed by command, struct or other validation scope. Empty
+/// when Vulkan-Docs was built without the `validusage` target.
+pub(crate) fn load() -> BTreeMap<String, Vec<ValidUsage>> {
+    let Ok(json) = std::fs::read_to_string(VALIDUSAGE_PATH) else {
+        println!("{VALIDUSAGE_PATH} not found, skipping explicit valid usage");
+        return BTreeMap::new();
+    };
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let mut scopes = BTreeMap::new();
+    for (scope, conditions) in json["validation"].as_object().unwrap() {
+        let statements: &mut Vec<ValidUsage> = scopes.entry(scope.clone()).or_default();
+        for (condition, entries) in conditions.as_object().unwrap() {
+            for entry in entries.as_array().unwrap() {
+                statements.push(ValidUsage {
+                    vuid: entry["vuid"].as_str().unwrap().to_string(),
+                    text: entry["text"].as_str().unwrap().to_string(),
+                    condition: (condition != "core").then(|| condition.clone()),
+                });
+            }
+        }
+        statements.sort_by_key(|statement| statement.condition.is_some());
+    }
+    scopes
+}
+
+impl Converter {
+    /// `valid_usage` lists the explicit statements of a page. Implicit ones, whose VUIDs end with
+    /// a name rather than a number, are already covered by `implicit_valid_usage`.
+    pub(crate) fn valid_usage_attributes(&self, name: &str) -> String {
+        let Some(statements) = self.valid_usage.get(name) else {
+            return String::new();
+        };
+        let explicit = Regex::new(r"-\d+$").unwrap();
+        let statements = statements
+            .iter()
+            .filter(|statement| explicit.is_match(&statement.vuid))
+            .map(|statement| {
+                let mut entry = format!(
+                    "{{\"vuid\": {}, \"text\": {}",
+                    json_string(&statement.vuid),
+                    json_string(&statement.text)
+                );
+                if let Some(condition) = &statement.condition {
+                    entry += &format!(", \"condition\": {}", json_string(condition));
+                    if let Some(requires) = parse_condition(condition) {
+                        entry += &format!(", \"requires\": {}", requires.to_json());
+                    }
+                }
+                entry + "}"
+            })
+            .collect::<Vec<_>>();
+        if statements.is_empty() {
+            return String::new();
+        }
+        format!("valid_usage: [{}]\n", statements.join(", "))
+    }
+
+    /// Writes every VUID of `validusage.json` with the command, struct or validation scope it
+    /// belongs to to `./dist/vuids.json`.
+    pub(crate) fn write_vuid_index(&self) {
+        let entries = self
+            .valid_usage
+            .iter()
+            .flat_map(|(scope, statements)| {
+                statements
+                    .iter()
+                    .map(move |statement| format!("{}: {}", json_string(&statement.vuid), json_string(scope)))
+            })
+            .collect::<Vec<_>>()
+            .join(",\n    ");
+        let mut file = File::create("./dist/vuids.json").unwrap();
+        file.write_fmt(format_args!("{{\n    {entries}\n}}\n")).unwrap();
+    }
+}
+
+/// A condition of `validusage.json` in the syntax of the `depends` attribute of vk.xml, where `,`
+/// means or, `+` means and and `!` negates.
+#[derive(Debug, PartialEq)]
+enum Condition {
+    Requirement(String),
+    Not(Box<Condition>),
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+}
+
+impl Condition {
+    /// A version or extension name as a string, `{"not": ...}`, `{"all": [...]}` or `{"any": [...]}`.
+    fn to_json(&self) -> String {
+        let list = |conditions: &[Condition]| {
+            conditions
+                .iter()
+                .map(Condition::to_json)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match self {
+            Condition::Requirement(name) => json_string(name),
+            Condition::Not(condition) => format!("{{\"not\": {}}}", condition.to_json()),
+            Condition::All(conditions) => format!("{{\"all\": [{}]}}", list(conditions)),
+            Condition::Any(conditions) => format!("{{\"any\": [{}]}}", list(conditions)),
+        }
+    }
+}
+
+fn parse_condition(condition: &str) -> Option<Condition> {
+    let (condition, rest) = parse_any(condition)?;
+    rest.is_empty().then_some(condition)
+}
+
+fn parse_any(input: &str) -> Option<(Condition, &str)> {
+    let (first, mut rest) = parse_all(input)?;
+    let mut conditions = vec![first];
+    while let Some(next) = rest.strip_prefix(',') {
+        let (condition, next) = parse_all(next)?;
+        conditions.push(condition);
+        rest = next;
+    }
+    let condition = if conditions.len() == 1 {
+        conditions.pop().unwrap()
+    } else {
+        Condition::Any(conditions)
+    };
+    Some((condition, rest))
+}
+
+fn parse_all(input: &str) -> Option<(Condition, &str)> {
+    let (first, mut rest) = parse_term(input)?;
+    let mut conditions = vec![first];
+    while let Some(next) = rest.strip_prefix('+') {
+        let (condition, next) = parse_term(next)?;
+        conditions.push(condition);
+        rest = next;
+    }
+    let condition = if conditions.len() == 1 {
+        conditions.pop().unwrap()
+    } else {
+        Condition::All(conditions)
+    };
+    Some((condition, rest))
+}
+
+fn parse_term(input: &str) -> Option<(Condition, &str)> {
+    let input = input.trim_start();
+    if let Some(rest) = input.strip_prefix('!') {
+        let (condition, rest) = parse_term(rest)?;
+        return Some((Condition::Not(Box::new(condition)), rest));
+    }
+    if let Some(rest) = input.strip_prefix('(') {
+        let (condition, rest) = parse_any(rest)?;
+        return Some((condition, rest.trim_start().strip_prefix(')')?.trim_start()));
+    }
+    let end = input
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == ':'))
+        .unwrap_or(input.len());
+    if end == 0 {
+        return None;
+    }
+    let (name, rest) = input.split_at(end);
+    Some((Condition::Requirement(name.to_string()), rest.trim_start()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn requirement(name: &str) -> Condition {
+        Condition::Requirement(name.to_string())
+    }
+
+    #[test]
+    fn or_of_requirements() {
+        assert_eq!(
+            parse_condition("(VK_VERSION_1_1,VK_KHR_device_group)"),
+            Some(Condition::Any(vec![
+                requirement("VK_VERSION_1_1"),
+                requirement("VK_KHR_device_group")
+            ]))
+        );
+    }
+
+    #[test]
+    fn and_with_negation() {
+        let condition = parse_condition("(VK_KHR_surface)+(!(VK_EXT_swapchain_maintenance1))").unwrap();
+        assert_eq!(
+            condition,
+            Condition::All(vec![
+                requirement("VK_KHR_surface"),
+                Condition::Not(Box::new(requirement("VK_EXT_swapchain_maintenance1")))
+            ])
+        );
+        assert_eq!(
+            condition.to_json(),
+            r#"{"all": ["VK_KHR_surface", {"not": "VK_EXT_swapchain_maintenance1"}]}"#
+        );
+    }
+
+    #[test]
+    fn unbalanced_parentheses() {
+        assert_eq!(parse_condition("(VK_VERSION_1_1"), None);
+    }
+}
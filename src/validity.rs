@@ -0,0 +1,490 @@
+//! Implicit valid usage statements, derived from the `optional`, `len`, `noautovalidity` and
+//! handle `parent` attributes of vk.xml following the rules of makeSpec's validity generator.
+
+use crate::{backend::extending_structs, c_decl, json_string, Converter};
+
+const MUST: &str = ":normative{type=\"must\"}";
+
+/// A command parameter or struct member, with the attributes implicit valid usage depends on.
+struct Field<'a> {
+    name: String,
+    decl: c_decl::CDecl,
+    len: Option<&'a str>,
+    optional: Option<&'a str>,
+    noautovalidity: bool,
+    values: Option<&'a str>,
+}
+
+impl Field<'_> {
+    /// Whether the value at the given level of indirection may be `NULL`, `VK_NULL_HANDLE` or 0.
+    fn is_optional(&self, level: usize) -> bool {
+        self.optional.and_then(|optional| optional.split(',').nth(level)) == Some("true")
+    }
+
+    fn is_pointer(&self) -> bool {
+        matches!(self.decl.ty, c_decl::CType::Pointer { .. })
+    }
+}
+
+enum Kind<'a> {
+    Handle,
+    Enum,
+    Bitmask(Option<&'a str>),
+    Struct,
+    FuncPointer,
+    Other,
+}
+
+impl Converter {
+    /// The implicit valid usage statements of a command or struct, as VUID and Markdown text.
+    pub(crate) fn implicit_valid_usage(&self, name: &str) -> Vec<(String, String)> {
+        let mut statements = Vec::new();
+        if self.commands.contains_key(name) {
+            let command = self.command_definition(name);
+            let fields = command
+                .params
+                .iter()
+                .filter(|param| param.api.as_ref().map(String::as_str) != Some("vulkansc"))
+                .map(|param| Field {
+                    name: param.definition.name.clone(),
+                    decl: c_decl::parse_declaration(&param.definition.code).unwrap(),
+                    len: param.len.as_deref(),
+                    optional: param.optional.as_deref(),
+                    noautovalidity: param.noautovalidity.as_deref() == Some("true"),
+                    values: None,
+                })
+                .collect::<Vec<_>>();
+            self.command_state_valid_usage(name, command, &mut statements);
+            self.fields_valid_usage(name, &fields, &mut statements);
+            self.parent_valid_usage(name, &fields, &mut statements);
+        } else if let Some(ty) = self.types.get(name) {
+            let vk_parse::TypeSpec::Members(members) = &ty.spec else {
+                return statements;
+            };
+            if ty.alias.is_some() || ty.category.as_deref() != Some("struct") {
+                return statements;
+            }
+            let fields = members
+                .iter()
+                .filter_map(|member| match member {
+                    vk_parse::TypeMember::Definition(def) => Some(def),
+                    _ => None,
+                })
+                .filter(|def| def.api.as_deref().map_or(true, |api| api == "vulkan"))
+                .map(|def| {
+                    let decl = c_decl::parse_declaration(&def.code).unwrap();
+                    Field {
+                        name: decl.name.clone().unwrap_or_default(),
+                        decl,
+                        len: def.len.as_deref(),
+                        optional: def.optional.as_deref(),
+                        noautovalidity: def.noautovalidity.as_deref() == Some("true"),
+                        values: def.values.as_deref(),
+                    }
+                })
+                .collect::<Vec<_>>();
+            self.chain_valid_usage(name, &fields, &mut statements);
+            // Structures returned by the implementation only have their chain validated.
+            if ty.returnedonly.as_deref() != Some("true") {
+                self.fields_valid_usage(name, &fields, &mut statements);
+            }
+        }
+        let mut seen = std::collections::HashSet::new();
+        statements.retain(|(vuid, _)| seen.insert(vuid.clone()));
+        statements
+    }
+
+    pub(crate) fn implicit_valid_usage_attributes(&self, name: &str) -> String {
+        let statements = self.implicit_valid_usage(name);
+        if statements.is_empty() {
+            return String::new();
+        }
+        let statements = statements
+            .iter()
+            .map(|(vuid, text)| format!("{{\"vuid\": {}, \"text\": {}}}", json_string(vuid), json_string(text)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("implicit_valid_usage: [{statements}]\n")
+    }
+
+    /// The Markdown of a `validity/protos` or `validity/structs` include: the implicit valid usage
+    /// and, for commands, the host synchronization box.
+    pub(crate) fn generate_validity(&self, name: &str) -> String {
+        let mut markdown = String::new();
+        let statements = self.implicit_valid_usage(name);
+        if !statements.is_empty() {
+            markdown += "\n::validity-group{name=\"Valid Usage (Implicit)\"}\n";
+            for (vuid, text) in statements {
+                markdown += &format!("\n::validity-field{{name=\"{vuid}\"}}\n{text}\n::\n");
+            }
+            markdown += "\n::\n";
+        }
+        if self.commands.contains_key(name) {
+            let command = self.command_definition(name);
+            let host_sync = self
+                .explicit_host_sync(command)
+                .into_iter()
+                .chain(self.implicit_host_sync(command))
+                .collect::<Vec<_>>();
+            if !host_sync.is_empty() {
+                markdown += "\n::validity-box{name=\"Host Synchronization\"}\n\n";
+                for statement in host_sync {
+                    markdown += &format!("- {statement}\n");
+                }
+                markdown += "\n::\n";
+            }
+        }
+        markdown
+    }
+
+    fn kind(&self, type_name: &str) -> Kind<'_> {
+        let type_name = self.resolve_type_alias(type_name);
+        match self.types.get(type_name).and_then(|ty| ty.category.as_deref()) {
+            Some("handle") => Kind::Handle,
+            Some("enum") => Kind::Enum,
+            Some("bitmask") => Kind::Bitmask(self.flag_bits_type(type_name)),
+            Some("struct") => Kind::Struct,
+            Some("funcpointer") => Kind::FuncPointer,
+            _ => Kind::Other,
+        }
+    }
+
+    /// `sType` and `pNext` of extendable structures.
+    fn chain_valid_usage(&self, page: &str, fields: &[Field], statements: &mut Vec<(String, String)>) {
+        for field in fields {
+            match field.name.as_str() {
+                "sType" => {
+                    if let Some(values) = field.values {
+                        statements.push((
+                            format!("VUID-{page}-sType-sType"),
+                            format!("`sType` {MUST} be `{values}`"),
+                        ));
+                    }
+                }
+                "pNext" if !field.noautovalidity => {
+                    let extending = extending_structs(self, page);
+                    if extending.is_empty() {
+                        statements.push((format!("VUID-{page}-pNext-pNext"), format!("`pNext` {MUST} be `NULL`")));
+                        continue;
+                    }
+                    let structs = extending
+                        .iter()
+                        .map(|name| type_link(name))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    statements.push((
+                        format!("VUID-{page}-pNext-pNext"),
+                        format!(
+                            "Each `pNext` member of any structure (including this one) in the `pNext` chain {MUST} be \
+                             either `NULL` or a pointer to a valid instance of {structs}"
+                        ),
+                    ));
+                    statements.push((
+                        format!("VUID-{page}-sType-unique"),
+                        format!("The `sType` value of each structure in the `pNext` chain {MUST} be unique"),
+                    ));
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// The `-parameter`, `-arraylength` and bitmask statements of each field.
+    fn fields_valid_usage(&self, page: &str, fields: &[Field], statements: &mut Vec<(String, String)>) {
+        for field in fields {
+            let name = field.name.as_str();
+            if field.noautovalidity || name == "sType" || name == "pNext" {
+                continue;
+            }
+            let vuid = format!("VUID-{page}-{name}-parameter");
+            let type_name = field.decl.ty.base_name();
+            match &field.decl.ty {
+                c_decl::CType::Named { .. } => {
+                    let valid = match self.kind(type_name) {
+                        Kind::Handle => format!("a valid {} handle", type_link(type_name)),
+                        Kind::Enum => format!("a valid {} value", type_link(type_name)),
+                        Kind::FuncPointer => format!("a valid {} value", type_link(type_name)),
+                        Kind::Struct if !self.implicit_valid_usage(type_name).is_empty() => {
+                            format!("a valid {} structure", type_link(type_name))
+                        }
+                        Kind::Bitmask(Some(bits)) => {
+                            if !field.is_optional(0) {
+                                statements.push((
+                                    format!("VUID-{page}-{name}-requiredbitmask"),
+                                    format!("`{name}` {MUST} not be `0`"),
+                                ));
+                            }
+                            format!("a valid combination of {} values", type_link(bits))
+                        }
+                        Kind::Bitmask(None) => {
+                            statements.push((format!("VUID-{page}-{name}-zerobitmask"), format!("`{name}` {MUST} be `0`")));
+                            continue;
+                        }
+                        _ => continue,
+                    };
+                    let condition = match self.kind(type_name) {
+                        Kind::Handle if field.is_optional(0) => format!("If `{name}` is not `VK_NULL_HANDLE`, "),
+                        Kind::FuncPointer if field.is_optional(0) => format!("If `{name}` is not `NULL`, "),
+                        _ => String::new(),
+                    };
+                    statements.push((vuid, format!("{condition}`{name}` {MUST} be {valid}")));
+                }
+                c_decl::CType::Array { .. } => {
+                    let valid = match self.kind(type_name) {
+                        Kind::Handle => format!("a valid {} handle", type_link(type_name)),
+                        Kind::Enum => format!("a valid {} value", type_link(type_name)),
+                        _ => continue,
+                    };
+                    statements.push((vuid, format!("Any given element of `{name}` {MUST} be {valid}")));
+                }
+                c_decl::CType::Pointer { pointee, .. } => {
+                    let Some(pointed) = self.pointer_target(field, pointee, fields, page, statements) else {
+                        continue;
+                    };
+                    statements.push((vuid, pointed));
+                }
+                c_decl::CType::Function { .. } => (),
+            }
+        }
+    }
+
+    /// The statement of a pointer field, adding the `-arraylength` statement of its length.
+    fn pointer_target(
+        &self,
+        field: &Field,
+        pointee: &c_decl::CType,
+        fields: &[Field],
+        page: &str,
+        statements: &mut Vec<(String, String)>,
+    ) -> Option<String> {
+        let name = field.name.as_str();
+        let type_name = pointee.base_name();
+        let is_input = pointee.is_const();
+        let lens = field.len.map(|len| len.split(',').collect::<Vec<_>>()).unwrap_or_default();
+        let null_terminated = lens.last() == Some(&"null-terminated");
+
+        if type_name == "char" && null_terminated && lens.len() == 1 {
+            let condition = if field.is_optional(0) { format!("If `{name}` is not `NULL`, ") } else { String::new() };
+            return Some(format!("{condition}`{name}` {MUST} be a null-terminated UTF-8 string"));
+        }
+        let Some(&len) = lens.first().filter(|len| **len != "null-terminated") else {
+            if matches!(pointee, c_decl::CType::Pointer { .. }) {
+                let condition = if field.is_optional(0) { format!("If `{name}` is not `NULL`, ") } else { String::new() };
+                return Some(format!("{condition}`{name}` {MUST} be a valid pointer to a pointer value"));
+            }
+            if pointee.is_void() {
+                return None;
+            }
+            let value = match (self.kind(type_name), is_input) {
+                (Kind::Struct, true) => format!("a valid {} structure", type_link(type_name)),
+                (Kind::Struct, false) => format!("a {} structure", type_link(type_name)),
+                (Kind::Handle, true) => format!("a valid {} handle", type_link(type_name)),
+                (Kind::Handle, false) => format!("a {} handle", type_link(type_name)),
+                (Kind::Enum, true) => format!("a valid {} value", type_link(type_name)),
+                (Kind::Bitmask(Some(bits)), true) => format!("a valid combination of {} values", type_link(bits)),
+                (Kind::Enum | Kind::Bitmask(_), false) | (Kind::Bitmask(None), true) => {
+                    format!("a {} value", type_link(type_name))
+                }
+                _ => format!("a `{type_name}` value"),
+            };
+            let condition = if field.is_optional(0) { format!("If `{name}` is not `NULL`, ") } else { String::new() };
+            return Some(format!("{condition}`{name}` {MUST} be a valid pointer to {value}"));
+        };
+        // Lengths given as a formula are left to the explicit valid usage.
+        if len.contains("latexmath") || len.contains('(') {
+            return None;
+        }
+
+        let mut conditions = Vec::new();
+        if let Some(count) = fields.iter().find(|count| count.name == len) {
+            if count.is_pointer() {
+                if count.is_optional(1) {
+                    conditions.push(format!("the value referenced by `{len}` is not `0`"));
+                }
+            } else if count.is_optional(0) {
+                conditions.push(format!("`{len}` is not `0`"));
+            } else if !count.noautovalidity {
+                statements.push((
+                    format!("VUID-{page}-{len}-arraylength"),
+                    format!("`{len}` {MUST} be greater than `0`"),
+                ));
+            }
+        }
+        if field.is_optional(0) {
+            conditions.push(format!("`{name}` is not `NULL`"));
+        }
+        let elements = if null_terminated {
+            "null-terminated UTF-8 strings".to_string()
+        } else {
+            match (self.kind(type_name), is_input) {
+                _ if pointee.is_void() => "bytes".to_string(),
+                (Kind::Struct, true) => format!("valid {} structures", type_link(type_name)),
+                (Kind::Struct, false) => format!("{} structures", type_link(type_name)),
+                (Kind::Handle, true) => format!("valid {} handles", type_link(type_name)),
+                (Kind::Handle, false) => format!("{} handles", type_link(type_name)),
+                (Kind::Enum, true) => format!("valid {} values", type_link(type_name)),
+                (Kind::Bitmask(Some(bits)), true) => format!("valid combinations of {} values", type_link(bits)),
+                (Kind::Enum | Kind::Bitmask(_), _) => format!("{} values", type_link(type_name)),
+                _ => format!("`{type_name}` values"),
+            }
+        };
+        let condition = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("If {}, ", conditions.join(", and "))
+        };
+        Some(format!(
+            "{condition}`{name}` {MUST} be a valid pointer to an array of `{len}` {elements}"
+        ))
+    }
+
+    /// `-parent` statements for handles created from another parameter, and the `commonparent`
+    /// statement for handles that must share an ancestor.
+    fn parent_valid_usage(&self, page: &str, fields: &[Field], statements: &mut Vec<(String, String)>) {
+        let handles = fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| !field.noautovalidity && matches!(self.kind(field.decl.ty.base_name()), Kind::Handle))
+            .filter(|(_, field)| match &field.decl.ty {
+                c_decl::CType::Named { .. } => true,
+                c_decl::CType::Pointer { pointee, .. } => pointee.is_const() && field.len.is_some(),
+                _ => false,
+            })
+            .collect::<Vec<_>>();
+        for (i, field) in handles.iter() {
+            let type_name = self.resolve_type_alias(field.decl.ty.base_name());
+            let parents = self
+                .types
+                .get(type_name)
+                .and_then(|ty| ty.parent.as_deref())
+                .map(|parents| parents.split(',').map(str::trim).collect::<Vec<_>>())
+                .unwrap_or_default();
+            if *i == 0 || parents.is_empty() {
+                continue;
+            }
+            let parent_param = handles.iter().find(|(j, parent)| {
+                j < i && !parent.is_pointer() && parents.contains(&self.resolve_type_alias(parent.decl.ty.base_name()))
+            });
+            let name = field.name.as_str();
+            if let Some((_, parent)) = parent_param {
+                let subject = if field.is_pointer() {
+                    format!("Each element of `{name}`")
+                } else if field.is_optional(0) {
+                    format!("If `{name}` is a valid handle, it")
+                } else {
+                    format!("`{name}`")
+                };
+                statements.push((
+                    format!("VUID-{page}-{name}-parent"),
+                    format!("{subject} {MUST} have been created, allocated, or retrieved from `{}`", parent.name),
+                ));
+            }
+        }
+
+        // As in makeSpec, handles whose type is an ancestor of another one are validated through
+        // that one, and the rest must share the first common ancestor of their parent chains.
+        let ancestors = handles
+            .iter()
+            .map(|(_, field)| {
+                let ancestry = self.handle_ancestry(field.decl.ty.base_name());
+                (*field, ancestry[0], ancestry[1..].to_vec())
+            })
+            .collect::<Vec<_>>();
+        let grouped = ancestors
+            .iter()
+            .filter(|(_, handle, _)| {
+                !ancestors
+                    .iter()
+                    .any(|(_, _, other_ancestors)| other_ancestors.contains(handle))
+            })
+            .collect::<Vec<_>>();
+        if grouped.len() < 2 {
+            return;
+        }
+        let Some(common_ancestor) = grouped[0].2.iter().find(|ancestor| {
+            grouped
+                .iter()
+                .all(|(_, _, other_ancestors)| other_ancestors.contains(*ancestor))
+        }) else {
+            return;
+        };
+        let mut names = grouped
+            .iter()
+            .map(|(field, _, _)| {
+                if field.is_pointer() {
+                    format!("the elements of `{}`", field.name)
+                } else {
+                    format!("`{}`", field.name)
+                }
+            })
+            .collect::<Vec<_>>();
+        names.sort();
+        let quantifier = if names.len() > 2 { "Each" } else { "Both" };
+        let last = names.pop().unwrap();
+        let any_optional = grouped.iter().any(|(field, _, _)| field.is_optional(0));
+        let qualifier = if any_optional {
+            " that are valid handles of non-ignored parameters"
+        } else {
+            ""
+        };
+        statements.push((
+            format!("VUID-{page}-commonparent"),
+            format!(
+                "{quantifier} of {}, and {last}{qualifier} {MUST} have been created, allocated, or retrieved \
+                 from the same {}",
+                names.join(", "),
+                type_link(common_ancestor)
+            ),
+        ));
+    }
+
+    /// Command buffer state, queue, render pass and video coding scope requirements of `vkCmd*`.
+    fn command_state_valid_usage(
+        &self,
+        page: &str,
+        command: &vk_parse::CommandDefinition,
+        statements: &mut Vec<(String, String)>,
+    ) {
+        if command.params.first().and_then(|param| param.definition.type_name.as_deref()) != Some("VkCommandBuffer")
+            || !page.starts_with("vkCmd")
+        {
+            return;
+        }
+        statements.push((
+            format!("VUID-{page}-commandBuffer-recording"),
+            format!("`commandBuffer` {MUST} be in the recording state"),
+        ));
+        if let Some(queues) = &command.queues {
+            let queues = queues.split(',').collect::<Vec<_>>().join(", or ");
+            statements.push((
+                format!("VUID-{page}-commandBuffer-cmdpool"),
+                format!(
+                    "The {} that `commandBuffer` was allocated from {MUST} support {queues} operations",
+                    type_link("VkCommandPool")
+                ),
+            ));
+        }
+        if let Some(scope @ ("inside" | "outside")) = command.renderpass.as_deref() {
+            statements.push((
+                format!("VUID-{page}-renderpass"),
+                format!("This command {MUST} only be called {scope} of a render pass instance"),
+            ));
+        }
+        if let Some(scope @ ("inside" | "outside")) = command.videocoding.as_deref() {
+            statements.push((
+                format!("VUID-{page}-videocoding"),
+                format!("This command {MUST} only be called {scope} of a video coding scope"),
+            ));
+        }
+        if let Some(level @ ("primary" | "secondary")) = command.cmdbufferlevel.as_deref() {
+            statements.push((
+                format!("VUID-{page}-bufferlevel"),
+                format!("`commandBuffer` {MUST} be a {level} {}", type_link("VkCommandBuffer")),
+            ));
+        }
+    }
+}
+
+fn type_link(name: &str) -> String {
+    format!("[{name}](/man/{name})")
+}